@@ -0,0 +1,222 @@
+/// Offline analyzer-pipeline benchmarking used by `Commands::Replay`.
+///
+/// Reads a previously recorded event log (one JSON object per line, the
+/// format `FileLogger` writes) and replays it through a configurable
+/// analyzer chain instead of driving a live eBPF runner, so filter
+/// patterns and SSE timeouts can be tuned offline and analyzer performance
+/// can be regression-tested without root or live traffic.
+use super::{Analyzer, AnalyzerError};
+use crate::framework::core::Event;
+use crate::framework::runners::EventStream;
+use futures::stream::{self, StreamExt};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Number of per-event latency samples kept per analyzer for the p99
+/// estimate; bounds memory use on long replays instead of recording every
+/// sample.
+const RESERVOIR_CAP: usize = 10_000;
+
+/// Running counters for a single analyzer stage in the replayed chain.
+pub struct StageStats {
+    name: String,
+    events_in: AtomicU64,
+    events_out: AtomicU64,
+    total_latency_ns: AtomicU64,
+    latencies_ns: Mutex<Vec<u64>>,
+}
+
+impl StageStats {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            events_in: AtomicU64::new(0),
+            events_out: AtomicU64::new(0),
+            total_latency_ns: AtomicU64::new(0),
+            latencies_ns: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn record_latency(&self, latency_ns: u64) {
+        self.total_latency_ns.fetch_add(latency_ns, Ordering::Relaxed);
+        let mut samples = self.latencies_ns.lock().unwrap();
+        if samples.len() < RESERVOIR_CAP {
+            samples.push(latency_ns);
+        }
+    }
+
+    /// Summarize this stage's counters into a printable report row.
+    fn report(&self) -> StageReport {
+        let events_in = self.events_in.load(Ordering::Relaxed);
+        let events_out = self.events_out.load(Ordering::Relaxed);
+        let total_ns = self.total_latency_ns.load(Ordering::Relaxed);
+
+        let mut samples = self.latencies_ns.lock().unwrap().clone();
+        samples.sort_unstable();
+
+        let mean_us = if events_out > 0 {
+            (total_ns as f64 / events_out as f64) / 1000.0
+        } else {
+            0.0
+        };
+
+        StageReport {
+            name: self.name.clone(),
+            events_in,
+            events_out,
+            dropped: events_in.saturating_sub(events_out),
+            mean_us,
+            p99_us: percentile_ns(&samples, 0.99) / 1000.0,
+        }
+    }
+}
+
+fn percentile_ns(sorted_samples: &[u64], p: f64) -> f64 {
+    if sorted_samples.is_empty() {
+        return 0.0;
+    }
+    let idx = ((sorted_samples.len() - 1) as f64 * p).round() as usize;
+    sorted_samples[idx.min(sorted_samples.len() - 1)] as f64
+}
+
+/// One row of [`run_replay`]'s printed report: how many events an analyzer
+/// saw, how many it passed on, and how long each took.
+#[derive(Debug, Clone)]
+pub struct StageReport {
+    pub name: String,
+    pub events_in: u64,
+    pub events_out: u64,
+    pub dropped: u64,
+    pub mean_us: f64,
+    pub p99_us: f64,
+}
+
+/// Total counters for a replay run, across the whole chain.
+#[derive(Debug, Clone)]
+pub struct ReplaySummary {
+    pub events_read: usize,
+    pub events_survived: usize,
+    pub elapsed: Duration,
+}
+
+/// Wrap `analyzer` so every event flowing through it is timed: each event
+/// is tagged with its arrival `Instant` on a FIFO queue right before
+/// `analyzer.process` sees it, and that queue is popped once per event the
+/// stage actually emits. This relies on stages not reordering events
+/// relative to each other, which holds for every analyzer in this chain -
+/// they only ever pass events through or filter them out in place.
+async fn instrument(
+    name: &'static str,
+    analyzer: &mut dyn Analyzer,
+    stream: EventStream,
+) -> Result<(EventStream, Arc<StageStats>), AnalyzerError> {
+    let stats = Arc::new(StageStats::new(name));
+    let starts: Arc<Mutex<VecDeque<Instant>>> = Arc::new(Mutex::new(VecDeque::new()));
+
+    let starts_in = Arc::clone(&starts);
+    let stats_in = Arc::clone(&stats);
+    let tagged: EventStream = Box::pin(stream.map(move |event| {
+        starts_in.lock().unwrap().push_back(Instant::now());
+        stats_in.events_in.fetch_add(1, Ordering::Relaxed);
+        event
+    }));
+
+    let processed = analyzer.process(tagged).await?;
+
+    let stats_out = Arc::clone(&stats);
+    let timed: EventStream = Box::pin(processed.map(move |event| {
+        if let Some(started) = starts.lock().unwrap().pop_front() {
+            stats_out.record_latency(started.elapsed().as_nanos() as u64);
+        }
+        stats_out.events_out.fetch_add(1, Ordering::Relaxed);
+        event
+    }));
+
+    Ok((timed, stats))
+}
+
+/// Parse one `FileLogger`-written log line back into an [`Event`], the way
+/// `RunnerRegistry` picks individual fields out of loosely-typed JSON
+/// rather than relying on a dedicated deserializer.
+fn parse_logged_event(line: &str) -> Option<Event> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let timestamp = value.get("timestamp").and_then(|v| v.as_u64())?;
+    let source = value.get("source").and_then(|v| v.as_str())?.to_string();
+    let pid = value.get("pid").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+    let comm = value.get("comm").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let data = value.get("data").cloned().unwrap_or(serde_json::Value::Null);
+    Some(Event::new_with_timestamp(timestamp, source, pid, comm, data))
+}
+
+/// Replay every event in `log_path` through `chain`, in order, timing each
+/// named analyzer stage. Returns one [`StageReport`] per stage plus a
+/// [`ReplaySummary`] for the whole run.
+pub async fn run_replay(
+    log_path: &str,
+    chain: Vec<(&'static str, Box<dyn Analyzer>)>,
+) -> Result<(Vec<StageReport>, ReplaySummary), AnalyzerError> {
+    let contents = std::fs::read_to_string(log_path)
+        .map_err(|e| format!("Failed to read replay log {}: {}", log_path, e))?;
+
+    let events: Vec<Event> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(parse_logged_event)
+        .collect();
+    let events_read = events.len();
+
+    let mut current: EventStream = Box::pin(stream::iter(events));
+    let mut stage_stats = Vec::with_capacity(chain.len());
+
+    let start = Instant::now();
+    for (name, mut analyzer) in chain {
+        let (next, stats) = instrument(name, analyzer.as_mut(), current).await?;
+        current = next;
+        stage_stats.push(stats);
+    }
+
+    // Drive the fully-wired pipeline to completion so every stage's
+    // instrumentation actually runs.
+    let events_survived = current.count().await;
+    let elapsed = start.elapsed();
+
+    let reports = stage_stats.iter().map(|s| s.report()).collect();
+    let summary = ReplaySummary {
+        events_read,
+        events_survived,
+        elapsed,
+    };
+
+    Ok((reports, summary))
+}
+
+/// Print a replay run's report in the same terse style the CLI uses for
+/// other summaries (see `print_global_http_filter_metrics`).
+pub fn print_replay_report(reports: &[StageReport], summary: &ReplaySummary) {
+    println!("Replay Benchmark Report");
+    println!("{}", "=".repeat(70));
+    println!("Events read from log:       {}", summary.events_read);
+    println!("Events surviving the chain: {}", summary.events_survived);
+
+    let elapsed_secs = summary.elapsed.as_secs_f64();
+    if elapsed_secs > 0.0 {
+        println!(
+            "Overall throughput:         {:.1} events/sec",
+            summary.events_read as f64 / elapsed_secs
+        );
+    }
+    println!();
+
+    println!(
+        "{:<24} {:>10} {:>10} {:>10} {:>12} {:>10}",
+        "Analyzer", "in", "out", "dropped", "mean us/ev", "p99 us"
+    );
+    for r in reports {
+        println!(
+            "{:<24} {:>10} {:>10} {:>10} {:>12.2} {:>10.2}",
+            r.name, r.events_in, r.events_out, r.dropped, r.mean_us, r.p99_us
+        );
+    }
+}
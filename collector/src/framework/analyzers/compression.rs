@@ -0,0 +1,144 @@
+use super::{Analyzer, AnalyzerError};
+use crate::framework::runners::EventStream;
+use async_trait::async_trait;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::stream::StreamExt;
+use std::io::Write;
+
+/// Compression algorithms supported across the framework. `FileLogger`'s
+/// streaming log writer and `CompressionAnalyzer` both compress through
+/// [`compress_bytes`] so the two stay consistent as more algorithms are added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    Gzip,
+}
+
+/// Compress a single buffer with the given algorithm and level (0-9).
+pub fn compress_bytes(algorithm: CompressionAlgorithm, level: u32, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    match algorithm {
+        CompressionAlgorithm::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level.min(9)));
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+    }
+}
+
+/// CompressionAnalyzer compresses each event's serialized JSON payload and
+/// *replaces* `event.data` with the result (base64-encoded) under
+/// `_compressed` / `_compressed_algorithm`, so that downstream sinks (file,
+/// network) forward the smaller payload instead of every sink
+/// reimplementing its own gzip plumbing. Since this discards the original
+/// fields, it belongs at (or near) the end of an analyzer chain, after
+/// anything that still needs to read `event.data`'s normal shape.
+pub struct CompressionAnalyzer {
+    algorithm: CompressionAlgorithm,
+    level: u32,
+}
+
+impl CompressionAnalyzer {
+    /// Create a new CompressionAnalyzer using gzip at the default level (6)
+    pub fn new() -> Self {
+        Self {
+            algorithm: CompressionAlgorithm::Gzip,
+            level: 6,
+        }
+    }
+
+    /// Override the compression level (0-9, clamped)
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = level.min(9);
+        self
+    }
+}
+
+impl Default for CompressionAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Analyzer for CompressionAnalyzer {
+    async fn process(&mut self, stream: EventStream) -> Result<EventStream, AnalyzerError> {
+        let algorithm = self.algorithm;
+        let level = self.level;
+
+        let processed_stream = stream.map(move |mut event| {
+            if let Ok(json_str) = event.to_json() {
+                match compress_bytes(algorithm, level, json_str.as_bytes()) {
+                    Ok(compressed) => {
+                        // Replace the payload rather than appending to it -
+                        // the whole point is to shrink what downstream sinks
+                        // forward, and keeping the uncompressed original
+                        // alongside the compressed copy would only grow it.
+                        event.data = serde_json::json!({
+                            "_compressed": base64::encode(&compressed),
+                            "_compressed_algorithm": "gzip",
+                        });
+                    }
+                    Err(e) => {
+                        log::warn!("CompressionAnalyzer: failed to compress event: {}", e);
+                    }
+                }
+            }
+            event
+        });
+
+        Ok(Box::pin(processed_stream))
+    }
+
+    fn name(&self) -> &str {
+        "CompressionAnalyzer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::core::Event;
+    use futures::stream;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_compression_analyzer_replaces_data_with_compressed_payload() {
+        let mut analyzer = CompressionAnalyzer::new();
+
+        let event = Event::new(
+            "test".to_string(),
+            1234,
+            "test".to_string(),
+            json!({"message": "hello world, hello world, hello world"}),
+        );
+
+        let input_stream: EventStream = Box::pin(stream::iter(vec![event]));
+        let output_stream = analyzer.process(input_stream).await.unwrap();
+        let collected: Vec<_> = output_stream.collect().await;
+
+        assert_eq!(collected.len(), 1);
+        let compressed = collected[0].data.get("_compressed").unwrap().as_str().unwrap();
+        assert!(!compressed.is_empty());
+        assert_eq!(
+            collected[0].data.get("_compressed_algorithm").unwrap(),
+            "gzip"
+        );
+
+        // The original field is gone - compression replaces the payload,
+        // it doesn't just tack a compressed copy on alongside it.
+        assert!(collected[0].data.get("message").is_none());
+        assert_eq!(collected[0].data.as_object().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_compress_bytes_roundtrip() {
+        let data = b"the quick brown fox jumps over the lazy dog";
+        let compressed = compress_bytes(CompressionAlgorithm::Gzip, 6, data).unwrap();
+        assert!(!compressed.is_empty());
+
+        let mut decoder = flate2::read::GzDecoder::new(&compressed[..]);
+        let mut decompressed = Vec::new();
+        std::io::Read::read_to_end(&mut decoder, &mut decompressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+}
@@ -0,0 +1,276 @@
+/// Batches events and forwards them to an external OTLP/Vector-style HTTP
+/// collector over `POST`, wired up via `--forward-url`/`--forward-format`.
+///
+/// Like [`FileLogger`](super::file_logger::FileLogger), this taps the stream
+/// without altering it; unlike `FileLogger` it hands events off to a
+/// background task over a bounded channel so a slow/unreachable collector
+/// never blocks the pipeline. The channel is the backpressure point: once
+/// it's full, new events are dropped and counted in `FORWARD_DROPPED`
+/// (the same "drop and count, never block" policy `main.rs::forward_event`
+/// uses for lagging broadcast subscribers), not queued without bound.
+///
+/// HTTP delivery goes through `hyper::Client`, reusing the dependency
+/// already pulled in by [`sse_sink`](super::super::runners::sse_sink)'s
+/// server; only plain `http://` collectors are supported; there's no
+/// `hyper-tls`/`hyper-rustls` connector wired up for `https://` yet.
+use super::{Analyzer, AnalyzerError};
+use crate::framework::core::Event;
+use crate::framework::runners::agent::jittered;
+use crate::framework::runners::EventStream;
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use hyper::{Body, Client, Method, Request};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Wire format used to serialize a batch before it's POSTed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardFormat {
+    /// One JSON object per line (newline-delimited JSON).
+    Ndjson,
+    /// A minimal OTLP-log-record-shaped envelope:
+    /// `{"resourceLogs": [{"scopeLogs": [{"logRecords": [...]}]}]}`, with
+    /// each record's `body` holding the raw event JSON. Good enough for
+    /// collectors (e.g. the OpenTelemetry Collector's `otlphttp` receiver)
+    /// that just want valid OTLP shape rather than semantic fidelity.
+    Otlp,
+}
+
+impl ForwardFormat {
+    /// Parse a `--forward-format` value. Unrecognized values fall back to
+    /// `Ndjson` rather than erroring out, since a typo shouldn't take down
+    /// an otherwise-working trace session.
+    pub fn parse(raw: &str) -> Self {
+        match raw.to_ascii_lowercase().as_str() {
+            "otlp" => ForwardFormat::Otlp,
+            _ => ForwardFormat::Ndjson,
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            ForwardFormat::Ndjson => "application/x-ndjson",
+            ForwardFormat::Otlp => "application/json",
+        }
+    }
+
+    fn encode(self, batch: &[Event]) -> String {
+        match self {
+            ForwardFormat::Ndjson => batch
+                .iter()
+                .filter_map(|event| event.to_json().ok())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ForwardFormat::Otlp => {
+                let records: Vec<serde_json::Value> = batch
+                    .iter()
+                    .filter_map(|event| event.to_json().ok())
+                    .filter_map(|json_str| serde_json::from_str::<serde_json::Value>(&json_str).ok())
+                    .map(|body| serde_json::json!({ "body": body }))
+                    .collect();
+                serde_json::json!({
+                    "resourceLogs": [{
+                        "scopeLogs": [{
+                            "logRecords": records,
+                        }],
+                    }],
+                })
+                .to_string()
+            }
+        }
+    }
+}
+
+/// Configuration for [`ForwardAnalyzer`].
+#[derive(Debug, Clone)]
+pub struct ForwardConfig {
+    pub url: String,
+    pub format: ForwardFormat,
+    /// Flush a batch once it reaches this many events...
+    pub batch_size: usize,
+    /// ...or once the buffer has gone this long without a new event,
+    /// whichever comes first.
+    pub batch_timeout: Duration,
+    /// Bounded channel capacity between the analyzer and the sender task;
+    /// the backpressure point described on [`ForwardAnalyzer`].
+    pub queue_capacity: usize,
+    /// Delivery attempts per batch before it's dropped and counted.
+    pub max_retries: u32,
+}
+
+impl Default for ForwardConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            format: ForwardFormat::Ndjson,
+            batch_size: 100,
+            batch_timeout: Duration::from_secs(5),
+            queue_capacity: 1000,
+            max_retries: 3,
+        }
+    }
+}
+
+static FORWARD_SENT: OnceLock<AtomicU64> = OnceLock::new();
+static FORWARD_DROPPED: OnceLock<AtomicU64> = OnceLock::new();
+static FORWARD_FAILED_BATCHES: OnceLock<AtomicU64> = OnceLock::new();
+
+fn sent_counter() -> &'static AtomicU64 {
+    FORWARD_SENT.get_or_init(|| AtomicU64::new(0))
+}
+
+fn dropped_counter() -> &'static AtomicU64 {
+    FORWARD_DROPPED.get_or_init(|| AtomicU64::new(0))
+}
+
+fn failed_batches_counter() -> &'static AtomicU64 {
+    FORWARD_FAILED_BATCHES.get_or_init(|| AtomicU64::new(0))
+}
+
+/// Print how many events were forwarded/dropped and how many batches were
+/// abandoned after exhausting retries, the same way
+/// `print_global_http_filter_metrics`/`print_global_ssl_filter_metrics` are
+/// printed on shutdown.
+pub fn print_global_forward_metrics() {
+    println!(
+        "Forward: {} events sent, {} dropped (queue full), {} batches abandoned after retries",
+        sent_counter().load(Ordering::Relaxed),
+        dropped_counter().load(Ordering::Relaxed),
+        failed_batches_counter().load(Ordering::Relaxed),
+    );
+}
+
+async fn send_batch(client: &Client<hyper::client::HttpConnector>, config: &ForwardConfig, batch: &[Event]) -> Result<(), AnalyzerError> {
+    let body = config.format.encode(batch);
+    let mut delay = Duration::from_millis(250);
+
+    for attempt in 0..config.max_retries {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(&config.url)
+            .header("content-type", config.format.content_type())
+            .body(Body::from(body.clone()))?;
+
+        match client.request(request).await {
+            Ok(response) if response.status().is_success() => {
+                sent_counter().fetch_add(batch.len() as u64, Ordering::Relaxed);
+                return Ok(());
+            }
+            Ok(response) => {
+                log::warn!(
+                    "Forward: collector at {} returned {} (attempt {}/{})",
+                    config.url,
+                    response.status(),
+                    attempt + 1,
+                    config.max_retries
+                );
+            }
+            Err(e) => {
+                log::warn!(
+                    "Forward: request to {} failed: {} (attempt {}/{})",
+                    config.url,
+                    e,
+                    attempt + 1,
+                    config.max_retries
+                );
+            }
+        }
+
+        if attempt + 1 < config.max_retries {
+            tokio::time::sleep(jittered(delay)).await;
+            delay = delay.saturating_mul(2);
+        }
+    }
+
+    failed_batches_counter().fetch_add(1, Ordering::Relaxed);
+    Err(format!("gave up forwarding batch of {} events to {} after {} attempts", batch.len(), config.url, config.max_retries).into())
+}
+
+async fn run_sender(config: ForwardConfig, mut rx: mpsc::Receiver<Event>) {
+    let client = Client::new();
+    let mut batch: Vec<Event> = Vec::with_capacity(config.batch_size);
+
+    loop {
+        let flush_batch = tokio::select! {
+            event = rx.recv() => {
+                match event {
+                    Some(event) => {
+                        batch.push(event);
+                        batch.len() >= config.batch_size
+                    }
+                    None => {
+                        // Channel closed: flush whatever's left and stop.
+                        if !batch.is_empty() {
+                            let _ = send_batch(&client, &config, &batch).await;
+                        }
+                        return;
+                    }
+                }
+            }
+            _ = tokio::time::sleep(config.batch_timeout), if !batch.is_empty() => true,
+        };
+
+        if flush_batch && !batch.is_empty() {
+            if let Err(e) = send_batch(&client, &config, &batch).await {
+                log::warn!("{}", e);
+            }
+            batch.clear();
+        }
+    }
+}
+
+/// Global analyzer that batches events and forwards them to an external
+/// HTTP collector. Add it to a runner's (or `AgentRunner`'s global)
+/// analyzer chain the same way [`MetricsCollector`](super::metrics::MetricsCollector)
+/// is added; construct with [`ForwardAnalyzer::new`].
+pub struct ForwardAnalyzer {
+    sender: Option<mpsc::Sender<Event>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl ForwardAnalyzer {
+    pub fn new(config: ForwardConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.queue_capacity);
+        let join_handle = tokio::spawn(run_sender(config, rx));
+        Self {
+            sender: Some(tx),
+            join_handle: Some(join_handle),
+        }
+    }
+}
+
+#[async_trait]
+impl Analyzer for ForwardAnalyzer {
+    async fn process(&mut self, stream: EventStream) -> Result<EventStream, AnalyzerError> {
+        let sender = self.sender.clone().expect("ForwardAnalyzer sender dropped before process()");
+
+        let processed_stream = stream.map(move |event| {
+            if let Err(mpsc::error::TrySendError::Full(_)) = sender.try_send(event.clone()) {
+                dropped_counter().fetch_add(1, Ordering::Relaxed);
+            }
+            event
+        });
+
+        Ok(Box::pin(processed_stream))
+    }
+
+    /// Close the channel to the sender task and wait (with a grace period)
+    /// for it to flush its final partial batch, mirroring how
+    /// `consume_with_shutdown` joins the web server in `main.rs`.
+    async fn flush(&mut self) -> Result<(), AnalyzerError> {
+        self.sender.take();
+        if let Some(handle) = self.join_handle.take() {
+            if tokio::time::timeout(Duration::from_secs(5), handle).await.is_err() {
+                return Err("forward sender did not flush within the grace period".into());
+            }
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "ForwardAnalyzer"
+    }
+}
@@ -1,55 +1,149 @@
+use super::metrics::{record_analyzer_process, record_filter_match, AnalyzerOutcome};
 use super::{Analyzer, AnalyzerError};
+use crate::framework::core::Event;
 use crate::framework::runners::EventStream;
 use async_trait::async_trait;
 use futures::stream::StreamExt;
+use regex::Regex;
 use serde_json::Value;
-use std::sync::{Arc, Mutex};
-
-// Global metrics storage for HTTP filter
-static HTTP_FILTER_GLOBAL_METRICS: std::sync::OnceLock<Arc<Mutex<FilterMetrics>>> = std::sync::OnceLock::new();
-
-/// Print global HTTP filter metrics
-pub fn print_global_http_filter_metrics() {
-    if let Some(metrics_ref) = HTTP_FILTER_GLOBAL_METRICS.get() {
-        if let Ok(metrics) = metrics_ref.lock() {
-            println!("[HTTPFilter Global Metrics] Total: {}, Filtered: {}, Passed: {}", 
-                     metrics.total_events_processed, 
-                     metrics.filtered_events_count, 
-                     metrics.passed_events_count);
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+static ALERT_DROPPED: OnceLock<AtomicU64> = OnceLock::new();
+
+fn alert_dropped_counter() -> &'static AtomicU64 {
+    ALERT_DROPPED.get_or_init(|| AtomicU64::new(0))
+}
+
+/// How serious a matched [`Rule`] is, carried through into its
+/// [`RuleDiagnostic`] and used to pick the `log` level it's reported at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for RuleSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleSeverity::Info => write!(f, "info"),
+            RuleSeverity::Warning => write!(f, "warning"),
+            RuleSeverity::Error => write!(f, "error"),
         }
-    } else {
-        println!("[HTTPFilter Global Metrics] No metrics available");
     }
 }
 
-/// Update global metrics with current filter metrics
-fn update_global_metrics(total: u64, filtered: u64, passed: u64) {
-    if let Some(metrics_ref) = HTTP_FILTER_GLOBAL_METRICS.get() {
-        if let Ok(mut metrics) = metrics_ref.lock() {
-            metrics.total_events_processed = total;
-            metrics.filtered_events_count = filtered;
-            metrics.passed_events_count = passed;
+/// What to do with an event once its [`Rule`]'s expression matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+    /// Drop the event - the only outcome the old binary exclude-pattern gate had.
+    Drop,
+    /// Let the event through unchanged; useful to carve out an allow-listed
+    /// exception ahead of a later, broader `Drop` rule.
+    Pass,
+    /// Null out the named top-level field in `event.data` (e.g. a header or
+    /// body field) before passing the event through.
+    Redact(String),
+    /// Annotate `event.data` with the matched rule's name under
+    /// `http_filter_tag`, for downstream analyzers to key off of, then pass
+    /// the event through.
+    Tag(String),
+    /// Pass the event through unchanged, and additionally hand a copy of it
+    /// to the alert sink set up via [`HTTPFilter::with_alert_sink`].
+    Alert,
+}
+
+/// A single named policy rule: a match expression, how severe a match is,
+/// and what to do about it.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub id: String,
+    pub expression: FilterExpression,
+    pub severity: RuleSeverity,
+    pub action: RuleAction,
+}
+
+impl Rule {
+    pub fn new(id: impl Into<String>, expression: FilterExpression, severity: RuleSeverity, action: RuleAction) -> Self {
+        Self {
+            id: id.into(),
+            expression,
+            severity,
+            action,
         }
     }
 }
 
-/// HTTP Filter Analyzer that filters HTTP parser events based on configurable expressions
-/// Similar to Python filter_expression.py but integrated into the Rust framework
+/// Structured record of a [`Rule`] match, reported through `log` at a level
+/// matching the rule's [`RuleSeverity`] in place of the old debug-only
+/// `eprintln!`.
+#[derive(Debug, Clone)]
+pub struct RuleDiagnostic {
+    pub rule_id: String,
+    pub severity: RuleSeverity,
+    pub expression: String,
+    pub event_source: String,
+}
+
+impl std::fmt::Display for RuleDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rule `{}` ({}) matched `{}` on event from `{}`",
+            self.rule_id, self.severity, self.expression, self.event_source
+        )
+    }
+}
+
+fn log_diagnostic(diagnostic: &RuleDiagnostic) {
+    match diagnostic.severity {
+        RuleSeverity::Info => log::info!("{}", diagnostic),
+        RuleSeverity::Warning => log::warn!("{}", diagnostic),
+        RuleSeverity::Error => log::error!("{}", diagnostic),
+    }
+}
+
+/// An event handed to the alert sink by a matched `RuleAction::Alert`,
+/// paired with the diagnostic that explains why.
+#[derive(Debug, Clone)]
+pub struct AlertedEvent {
+    pub event: Event,
+    pub diagnostic: RuleDiagnostic,
+}
+
+/// HTTP Filter Analyzer: a first-match-wins policy engine over HTTP parser
+/// events, evaluating an ordered list of [`Rule`]s against each event and
+/// applying the first matching [`RuleAction`].
+///
+/// Metrics (total/filtered/passed counters, per-event latency, and
+/// per-expression match counts) are reported through the crate-wide
+/// registry in [`super::metrics`], keyed by [`Analyzer::name`], rather than
+/// a filter-specific global - see [`record_analyzer_process`]/
+/// [`record_filter_match`] and `render_global_prometheus_metrics`.
 #[derive(Debug)]
 pub struct HTTPFilter {
-    /// Filter expressions to exclude events
+    /// Original patterns passed to [`HTTPFilter::with_patterns`], kept for
+    /// diagnostics/introspection.
     exclude_patterns: Vec<String>,
-    /// Compiled filter expressions
-    filters: Vec<FilterExpression>,
-    /// Debug mode
-    debug: bool,
-    /// Metrics (shared atomic counters for thread safety)
-    total_events_processed: std::sync::Arc<std::sync::atomic::AtomicU64>,
-    filtered_events_count: std::sync::Arc<std::sync::atomic::AtomicU64>,
-    passed_events_count: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Ordered policy rules, evaluated first-match-wins.
+    rules: Vec<Rule>,
+    /// Set by [`HTTPFilter::with_alert_sink`]; `RuleAction::Alert` matches
+    /// are handed to this channel instead of just logged.
+    alert_sender: Option<mpsc::Sender<AlertedEvent>>,
 }
 
-/// A single filter expression that can evaluate HTTP events
+/// A single filter expression that can evaluate HTTP events.
+///
+/// Grammar (lowest to highest precedence): `or := and ('|' and)*`,
+/// `and := unary ('&' unary)*`, `unary := '!'? primary`,
+/// `primary := '(' or ')' | condition`. A condition is `key op value`,
+/// where `key` is either dot notation (`request.path`, `response.status_code`)
+/// or a legacy bare field name (assumed to target the request), `op` is one
+/// of `=`, `!=`, `=~` (regex), `>`, `<`, `>=`, `<=`, and `value` may be
+/// wrapped in double quotes to include `| & ( ) !` literally.
 #[derive(Debug, Clone)]
 pub struct FilterExpression {
     /// Original expression string
@@ -65,164 +159,379 @@ pub enum FilterNode {
     And(Vec<FilterNode>),
     /// Logical OR operation
     Or(Vec<FilterNode>),
+    /// Logical negation
+    Not(Box<FilterNode>),
     /// Single condition
     Condition {
         target: String,      // "request" or "response"
         field: String,       // "method", "path", "status_code", etc.
-        operator: String,    // "=", "contains", "prefix", etc.
-        value: String,       // Expected value
+        operator: String,    // "exact", "contains", "prefix", "not_equal", "regex", "gt", "lt", "gte", "lte"
+        value: String,       // Expected value (the regex source, for "regex")
+        regex: Option<Regex>, // Compiled once at parse time when operator == "regex"
     },
     /// Empty filter (matches nothing)
     Empty,
 }
 
+/// Why [`FilterExpression::parse`] rejected an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterParseErrorReason {
+    /// A `(` was never closed by a matching `)` (or vice versa).
+    UnterminatedParenthesis,
+    /// A `"` was opened but never closed.
+    UnterminatedQuote,
+    /// An `&`/`|` had nothing (or only whitespace) on one side.
+    EmptyOperand,
+    /// The pattern after `=~` isn't a valid regular expression.
+    InvalidRegex(String),
+}
+
+impl std::fmt::Display for FilterParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnterminatedParenthesis => write!(f, "unterminated parenthesis"),
+            Self::UnterminatedQuote => write!(f, "unterminated quote"),
+            Self::EmptyOperand => write!(f, "empty operand around `&`/`|`"),
+            Self::InvalidRegex(err) => write!(f, "invalid regex: {}", err),
+        }
+    }
+}
+
+/// A malformed filter expression, carrying enough context (the original
+/// text, a byte offset into it, and the reason) to point a user at exactly
+/// what's wrong instead of the expression silently collapsing to
+/// [`FilterNode::Empty`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterParseError {
+    expression: String,
+    offset: usize,
+    reason: FilterParseErrorReason,
+}
+
+impl FilterParseError {
+    fn new(expression: &str, offset: usize, reason: FilterParseErrorReason) -> Self {
+        Self { expression: expression.to_string(), offset, reason }
+    }
+
+    /// Byte offset into the original expression where the problem starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Why the expression was rejected.
+    pub fn reason(&self) -> &FilterParseErrorReason {
+        &self.reason
+    }
+}
+
+impl std::fmt::Display for FilterParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid filter expression `{}` at byte {}: {}", self.expression, self.offset, self.reason)
+    }
+}
+
+impl std::error::Error for FilterParseError {}
+
 impl HTTPFilter {
-    /// Create a new HTTP filter with no patterns (passes everything through)
+    /// Create a new HTTP filter with no rules (passes everything through)
     pub fn new() -> Self {
-        // Initialize global metrics if not already done
-        let _ = HTTP_FILTER_GLOBAL_METRICS.set(Arc::new(Mutex::new(FilterMetrics {
-            total_events_processed: 0,
-            filtered_events_count: 0,
-            passed_events_count: 0,
-        })));
-
         HTTPFilter {
             exclude_patterns: Vec::new(),
-            filters: Vec::new(),
-            debug: false,
-            total_events_processed: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
-            filtered_events_count: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
-            passed_events_count: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            rules: Vec::new(),
+            alert_sender: None,
         }
     }
 
-    /// Create a new HTTP filter with exclude patterns
-    pub fn with_patterns(patterns: Vec<String>) -> Self {
+    /// Create a new HTTP filter with legacy exclude patterns: each pattern
+    /// becomes a `Drop` rule with `RuleSeverity::Warning`, matching the old
+    /// binary drop/pass behavior for callers that only pass `--http-filter`
+    /// strings rather than building [`Rule`]s directly.
+    ///
+    /// Returns the first malformed pattern's [`FilterParseError`] instead of
+    /// silently compiling it down to a rule that never matches.
+    pub fn with_patterns(patterns: Vec<String>) -> Result<Self, FilterParseError> {
         let mut filter = HTTPFilter::new();
         filter.exclude_patterns = patterns.clone();
-        filter.filters = patterns.into_iter()
-            .map(|p| FilterExpression::parse(&p))
-            .collect();
+        filter.rules = patterns
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| -> Result<Rule, FilterParseError> {
+                Ok(Rule::new(format!("pattern-{i}"), FilterExpression::parse(&p)?, RuleSeverity::Warning, RuleAction::Drop))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(filter)
+    }
+
+    /// Create a new HTTP filter from an explicit, ordered set of policy rules.
+    pub fn with_rules(rules: Vec<Rule>) -> Self {
+        let mut filter = HTTPFilter::new();
+        filter.rules = rules;
         filter
     }
 
+    /// Wire `RuleAction::Alert` matches to a bounded channel and return its
+    /// receiving half, for the caller to forward however it likes (log it,
+    /// feed it into [`super::forward::ForwardAnalyzer`], etc.). Once the
+    /// channel is full, further alerts are dropped and counted in
+    /// `ALERT_DROPPED` - the same "drop and count, never block" backpressure
+    /// policy [`super::forward::ForwardAnalyzer`] uses for its own queue.
+    pub fn with_alert_sink(mut self, capacity: usize) -> (Self, mpsc::Receiver<AlertedEvent>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.alert_sender = Some(tx);
+        (self, rx)
+    }
+}
 
+/// Recursive-descent state for [`FilterExpression::parse`]; `input` is
+/// always a suffix of `full`'s own buffer, so [`Self::offset`] can recover a
+/// byte position for error reporting without threading one through every
+/// call (the same trick `ssl_filter`'s parser uses).
+struct ConditionParser<'a> {
+    full: &'a str,
+    input: &'a str,
+}
 
+impl<'a> ConditionParser<'a> {
+    fn offset(&self) -> usize {
+        (self.input.as_ptr() as usize).saturating_sub(self.full.as_ptr() as usize)
+    }
 
+    fn skip_ws(&mut self) {
+        self.input = self.input.trim_start();
+    }
 
+    fn peek(&self) -> Option<char> {
+        self.input.chars().next()
+    }
 
-}
+    fn bump(&mut self) -> Option<char> {
+        let mut chars = self.input.chars();
+        let c = chars.next()?;
+        self.input = chars.as_str();
+        Some(c)
+    }
 
-/// Metrics for HTTP filtering
-#[derive(Debug, Clone)]
-pub struct FilterMetrics {
-    pub total_events_processed: u64,
-    pub filtered_events_count: u64,
-    pub passed_events_count: u64,
-}
+    fn parse_or(&mut self) -> Result<FilterNode, FilterParseError> {
+        let mut nodes = vec![self.parse_and()?];
+        loop {
+            self.skip_ws();
+            if self.peek() != Some('|') {
+                break;
+            }
+            self.bump();
+            self.skip_ws();
+            if matches!(self.peek(), None | Some(')') | Some('|')) {
+                return Err(FilterParseError::new(self.full, self.offset(), FilterParseErrorReason::EmptyOperand));
+            }
+            nodes.push(self.parse_and()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.pop().unwrap() } else { FilterNode::Or(nodes) })
+    }
 
-impl FilterMetrics {
+    fn parse_and(&mut self) -> Result<FilterNode, FilterParseError> {
+        let mut nodes = vec![self.parse_unary()?];
+        loop {
+            self.skip_ws();
+            if self.peek() != Some('&') {
+                break;
+            }
+            self.bump();
+            self.skip_ws();
+            if matches!(self.peek(), None | Some(')') | Some('&') | Some('|')) {
+                return Err(FilterParseError::new(self.full, self.offset(), FilterParseErrorReason::EmptyOperand));
+            }
+            nodes.push(self.parse_unary()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.pop().unwrap() } else { FilterNode::And(nodes) })
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterNode, FilterParseError> {
+        self.skip_ws();
+        if self.peek() == Some('!') {
+            self.bump();
+            self.skip_ws();
+            let inner = self.parse_unary()?;
+            return Ok(FilterNode::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterNode, FilterParseError> {
+        self.skip_ws();
+        if self.peek() == Some('(') {
+            self.bump();
+            let node = self.parse_or()?;
+            self.skip_ws();
+            if self.peek() != Some(')') {
+                return Err(FilterParseError::new(self.full, self.offset(), FilterParseErrorReason::UnterminatedParenthesis));
+            }
+            self.bump();
+            return Ok(node);
+        }
+        self.parse_condition()
+    }
+
+    /// Consume a condition's raw text up to the next top-level `&`, `|` or
+    /// `)`, treating anything between a pair of double quotes as opaque so
+    /// values can contain `| & ( ) !` literally.
+    fn read_condition_raw(&mut self) -> Result<&'a str, FilterParseError> {
+        let start = self.input;
+        let mut in_quotes = false;
+        let mut end = start.len();
+        let mut found = false;
+
+        for (i, c) in start.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                '&' | '|' | ')' if !in_quotes => {
+                    end = i;
+                    found = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        if in_quotes && !found {
+            return Err(FilterParseError::new(self.full, self.offset(), FilterParseErrorReason::UnterminatedQuote));
+        }
+
+        self.input = &start[end..];
+        Ok(&start[..end])
+    }
+
+    fn parse_condition(&mut self) -> Result<FilterNode, FilterParseError> {
+        self.skip_ws();
+        let condition_offset = self.offset();
+        let raw = self.read_condition_raw()?;
+        let trimmed = raw.trim();
+
+        if trimmed.is_empty() {
+            return Err(FilterParseError::new(self.full, condition_offset, FilterParseErrorReason::EmptyOperand));
+        }
+
+        FilterExpression::parse_condition_text(self.full, condition_offset, trimmed)
+    }
 }
 
 impl FilterExpression {
-    /// Parse a filter expression string
-    pub fn parse(expression: &str) -> Self {
+    /// Parse a filter expression string, reporting a [`FilterParseError`]
+    /// (with a byte offset into `expression`) instead of silently falling
+    /// back to a filter that matches nothing.
+    pub fn parse(expression: &str) -> Result<Self, FilterParseError> {
         let trimmed = expression.trim();
         if trimmed.is_empty() {
-            return FilterExpression {
+            return Ok(FilterExpression {
                 expression: expression.to_string(),
                 parsed: FilterNode::Empty,
-            };
+            });
+        }
+
+        Self::check_parens_balanced(expression)?;
+
+        let mut parser = ConditionParser { full: expression, input: trimmed };
+        let parsed = parser.parse_or()?;
+        parser.skip_ws();
+        if !parser.input.is_empty() {
+            return Err(FilterParseError::new(expression, parser.offset(), FilterParseErrorReason::UnterminatedParenthesis));
         }
 
-        let parsed = Self::parse_or_expression(trimmed);
-        FilterExpression {
+        Ok(FilterExpression {
             expression: expression.to_string(),
             parsed,
-        }
+        })
     }
 
-    /// Parse OR expressions (lowest precedence)
-    fn parse_or_expression(expr: &str) -> FilterNode {
-        let or_parts: Vec<&str> = expr.split('|').map(|s| s.trim()).collect();
-        
-        if or_parts.len() > 1 {
-            let conditions: Vec<FilterNode> = or_parts.into_iter()
-                .map(|part| Self::parse_and_expression(part))
-                .collect();
-            FilterNode::Or(conditions)
-        } else {
-            Self::parse_and_expression(expr)
+    /// Verify every `(` has a matching `)` (and vice versa) before parsing
+    /// starts, ignoring parentheses inside double-quoted values, so an
+    /// unbalanced expression is reported with a precise location rather than
+    /// silently collapsing to [`FilterNode::Empty`].
+    fn check_parens_balanced(expr: &str) -> Result<(), FilterParseError> {
+        let mut open_positions = Vec::new();
+        let mut in_quotes = false;
+
+        for (i, c) in expr.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                '(' if !in_quotes => open_positions.push(i),
+                ')' if !in_quotes => {
+                    if open_positions.pop().is_none() {
+                        return Err(FilterParseError::new(expr, i, FilterParseErrorReason::UnterminatedParenthesis));
+                    }
+                }
+                _ => {}
+            }
         }
-    }
 
-    /// Parse AND expressions (higher precedence)
-    fn parse_and_expression(expr: &str) -> FilterNode {
-        let and_parts: Vec<&str> = expr.split('&').map(|s| s.trim()).collect();
-        
-        if and_parts.len() > 1 {
-            let conditions: Vec<FilterNode> = and_parts.into_iter()
-                .map(|part| Self::parse_condition(part))
-                .collect();
-            FilterNode::And(conditions)
-        } else {
-            Self::parse_condition(expr)
+        if let Some(&unclosed) = open_positions.first() {
+            return Err(FilterParseError::new(expr, unclosed, FilterParseErrorReason::UnterminatedParenthesis));
         }
+
+        Ok(())
     }
 
-    /// Parse a single condition
-    fn parse_condition(condition: &str) -> FilterNode {
-        let condition = condition.trim();
-        
-        if !condition.contains('=') {
-            // Simple path containment (legacy)
-            return FilterNode::Condition {
-                target: "request".to_string(),
-                field: "path".to_string(),
-                operator: "contains".to_string(),
-                value: condition.to_string(),
-            };
+    /// Find the leftmost occurrence of a comparison operator outside double
+    /// quotes. Longer operators are listed before their prefixes (`!=`/`>=`/
+    /// `<=`/`=~` before `=`) so e.g. `=~` is never mistaken for a bare `=`.
+    fn find_condition_operator(condition: &str) -> Option<(usize, &'static str)> {
+        const OPERATORS: &[&str] = &["!=", ">=", "<=", "=~", "=", ">", "<"];
+        let mut in_quotes = false;
+
+        for (i, _) in condition.char_indices() {
+            let c = condition[i..].chars().next().unwrap();
+            if c == '"' {
+                in_quotes = !in_quotes;
+                continue;
+            }
+            if in_quotes {
+                continue;
+            }
+            for op in OPERATORS {
+                if condition[i..].starts_with(op) {
+                    return Some((i, op));
+                }
+            }
         }
+        None
+    }
 
-        let parts: Vec<&str> = condition.splitn(2, '=').collect();
-        if parts.len() != 2 {
-            return FilterNode::Empty;
+    /// Strip a pair of surrounding double quotes, if present, revealing the
+    /// literal value the tokenizer protected from being split on `| & ( ) !`.
+    fn unquote(value: &str) -> String {
+        if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+            value[1..value.len() - 1].to_string()
+        } else {
+            value.to_string()
         }
+    }
 
-        let key = parts[0].trim();
-        let value = parts[1].trim();
-
-        // Parse dot notation (request.path, response.status_code)
-        if key.contains('.') {
-            let key_parts: Vec<&str> = key.splitn(2, '.').collect();
-            if key_parts.len() == 2 {
-                let target = key_parts[0].trim();
-                let field = key_parts[1].trim();
-                
-                let (target, operator) = if target == "request" || target == "req" {
-                    let op = match field {
-                        "path_prefix" | "path_starts_with" => "prefix",
-                        "path_contains" | "path_includes" => "contains",
-                        "path" | "path_exact" => "exact",
-                        _ => "exact",
-                    };
-                    ("request", op)
-                } else if target == "response" || target == "resp" || target == "res" {
-                    ("response", "exact")
-                } else {
-                    ("request", "exact")
-                };
+    /// Resolve a condition's key (dot notation like `request.path`, or a
+    /// legacy bare field name assumed to target the request) into a
+    /// `(target, field, default_operator)` triple, where `default_operator`
+    /// is what a bare `=` means for that field (e.g. `path_prefix=/admin`
+    /// means prefix matching even though the symbol itself is `=`).
+    fn resolve_target_field(key: &str) -> (String, String, &'static str) {
+        if let Some((target_part, field_part)) = key.split_once('.') {
+            let target_part = target_part.trim();
+            let field_part = field_part.trim();
 
-                return FilterNode::Condition {
-                    target: target.to_string(),
-                    field: field.to_string(),
-                    operator: operator.to_string(),
-                    value: value.to_string(),
+            let (target, operator) = if target_part == "request" || target_part == "req" {
+                let op = match field_part {
+                    "path_prefix" | "path_starts_with" => "prefix",
+                    "path_contains" | "path_includes" => "contains",
+                    "path" | "path_exact" => "exact",
+                    _ => "exact",
                 };
-            }
+                ("request", op)
+            } else if target_part == "response" || target_part == "resp" || target_part == "res" {
+                ("response", "exact")
+            } else {
+                ("request", "exact")
+            };
+
+            return (target.to_string(), field_part.to_string(), operator);
         }
 
-        // Legacy format (assume request)
         let operator = match key {
             "path_prefix" | "path_starts_with" => "prefix",
             "path_contains" | "path_includes" => "contains",
@@ -230,12 +539,60 @@ impl FilterExpression {
             _ => "exact",
         };
 
-        FilterNode::Condition {
-            target: "request".to_string(),
-            field: key.to_string(),
-            operator: operator.to_string(),
-            value: value.to_string(),
+        ("request".to_string(), key.to_string(), operator)
+    }
+
+    /// Parse a single condition's already-isolated text (e.g.
+    /// `response.status_code>=500` or `"/a&b"`) into a [`FilterNode`].
+    fn parse_condition_text(full: &str, offset: usize, condition: &str) -> Result<FilterNode, FilterParseError> {
+        let condition = condition.trim();
+
+        if condition.starts_with('(') && condition.ends_with(')') {
+            let mut parser = ConditionParser { full, input: &condition[1..condition.len() - 1] };
+            return parser.parse_or();
         }
+
+        let Some((op_pos, op)) = Self::find_condition_operator(condition) else {
+            // Legacy: no operator at all -> simple path containment.
+            return Ok(FilterNode::Condition {
+                target: "request".to_string(),
+                field: "path".to_string(),
+                operator: "contains".to_string(),
+                value: condition.to_string(),
+                regex: None,
+            });
+        };
+
+        let key = condition[..op_pos].trim();
+        let raw_value = condition[op_pos + op.len()..].trim();
+        let value = Self::unquote(raw_value);
+
+        let (target, field, default_operator) = Self::resolve_target_field(key);
+
+        let operator = match op {
+            "=" => default_operator,
+            "!=" => "not_equal",
+            "=~" => "regex",
+            ">" => "gt",
+            "<" => "lt",
+            ">=" => "gte",
+            "<=" => "lte",
+            _ => default_operator,
+        };
+
+        let regex = if operator == "regex" {
+            Some(Regex::new(&value).map_err(|e| FilterParseError::new(full, offset, FilterParseErrorReason::InvalidRegex(e.to_string())))?)
+        } else {
+            None
+        };
+
+        Ok(FilterNode::Condition {
+            target,
+            field,
+            operator: operator.to_string(),
+            value,
+            regex,
+        })
     }
 
     /// Evaluate the filter expression against event data
@@ -247,22 +604,23 @@ impl FilterExpression {
     fn evaluate_node(&self, node: &FilterNode, data: &Value) -> bool {
         match node {
             FilterNode::Empty => false,
+            FilterNode::Not(inner) => !self.evaluate_node(inner, data),
             FilterNode::And(conditions) => {
                 conditions.iter().all(|c| self.evaluate_node(c, data))
             }
             FilterNode::Or(conditions) => {
                 conditions.iter().any(|c| self.evaluate_node(c, data))
             }
-            FilterNode::Condition { target, field, operator, value } => {
-                self.evaluate_condition(target, field, operator, value, data)
+            FilterNode::Condition { target, field, operator, value, regex } => {
+                self.evaluate_condition(target, field, operator, value, regex.as_ref(), data)
             }
         }
     }
 
     /// Evaluate a single condition
-    fn evaluate_condition(&self, target: &str, field: &str, operator: &str, value: &str, data: &Value) -> bool {
+    fn evaluate_condition(&self, target: &str, field: &str, operator: &str, value: &str, regex: Option<&Regex>, data: &Value) -> bool {
         let message_type = data.get("message_type").and_then(|v| v.as_str()).unwrap_or("");
-        
+
         // Check if the data type matches the target
         let matches_target = match target {
             "request" => message_type == "request",
@@ -274,13 +632,73 @@ impl FilterExpression {
             return false;
         }
 
+        match operator {
+            "regex" => {
+                let actual = self.field_as_string(target, field, data).unwrap_or_default();
+                regex.map(|re| re.is_match(&actual)).unwrap_or(false)
+            }
+            "not_equal" => {
+                let actual = self.field_as_string(target, field, data).unwrap_or_default();
+                actual != value
+            }
+            "gt" | "lt" | "gte" | "lte" => {
+                // Fail closed: a comparison against a non-numeric field or
+                // value never matches, rather than falling back to a string
+                // comparison.
+                match (self.field_as_u64(target, field, data), value.parse::<u64>().ok()) {
+                    (Some(actual), Some(expected)) => match operator {
+                        "gt" => actual > expected,
+                        "lt" => actual < expected,
+                        "gte" => actual >= expected,
+                        "lte" => actual <= expected,
+                        _ => false,
+                    },
+                    _ => false,
+                }
+            }
+            _ if target == "request" => self.evaluate_request_condition(field, operator, value, data),
+            _ if target == "response" => self.evaluate_response_condition(field, operator, value, data),
+            _ => false,
+        }
+    }
+
+    /// Extract a field's value as a string, the same lookup
+    /// [`Self::evaluate_request_condition`]/[`Self::evaluate_response_condition`]
+    /// do per-field, but independent of the comparison operator - used by
+    /// `not_equal`/`regex`/numeric comparisons.
+    fn field_as_string(&self, target: &str, field: &str, data: &Value) -> Option<String> {
+        let empty_map = serde_json::Map::new();
+        let headers = || data.get("headers").and_then(|v| v.as_object()).unwrap_or(&empty_map);
+
         if target == "request" {
-            self.evaluate_request_condition(field, operator, value, data)
-        } else if target == "response" {
-            self.evaluate_response_condition(field, operator, value, data)
+            match field {
+                "method" | "verb" => data.get("method").and_then(|v| v.as_str()).map(str::to_string),
+                "path" | "path_exact" | "path_prefix" | "path_starts_with" | "path_contains" | "path_includes" => {
+                    data.get("path").and_then(|v| v.as_str()).map(str::to_string)
+                }
+                "host" | "hostname" => headers().get("host").and_then(|v| v.as_str()).map(str::to_string),
+                "body" | "body_contains" => data.get("body").and_then(|v| v.as_str()).map(str::to_string),
+                _ => headers().get(field).and_then(|v| v.as_str()).map(str::to_string),
+            }
         } else {
-            false
+            match field {
+                "status_code" | "status" | "code" => data.get("status_code").and_then(|v| v.as_u64()).map(|n| n.to_string()),
+                "status_text" | "status_message" => data.get("status_text").and_then(|v| v.as_str()).map(str::to_string),
+                "content_type" | "content-type" => headers().get("content-type").and_then(|v| v.as_str()).map(str::to_string),
+                "server" => headers().get("server").and_then(|v| v.as_str()).map(str::to_string),
+                "body" | "body_contains" => data.get("body").and_then(|v| v.as_str()).map(str::to_string),
+                _ => headers().get(field).and_then(|v| v.as_str()).map(str::to_string),
+            }
+        }
+    }
+
+    /// Like [`Self::field_as_string`] but parsed as `u64`, used by the
+    /// numeric comparison operators.
+    fn field_as_u64(&self, target: &str, field: &str, data: &Value) -> Option<u64> {
+        if target == "response" && matches!(field, "status_code" | "status" | "code") {
+            return data.get("status_code").and_then(|v| v.as_u64());
         }
+        self.field_as_string(target, field, data).and_then(|s| s.parse::<u64>().ok())
     }
 
     /// Evaluate request conditions
@@ -375,67 +793,74 @@ impl FilterExpression {
 #[async_trait]
 impl Analyzer for HTTPFilter {
     async fn process(&mut self, stream: EventStream) -> Result<EventStream, AnalyzerError> {
-        let filters = self.filters.clone();
-        let debug = self.debug;
-        
-        // Clone the shared atomic counters for use in the stream
-        let total_counter = self.total_events_processed.clone();
-        let filtered_counter = self.filtered_events_count.clone();
-        let passed_counter = self.passed_events_count.clone();
-        
-        let filtered_stream = stream.filter_map(move |event| {
-            let filters = filters.clone();
-            let total_counter = total_counter.clone();
-            let filtered_counter = filtered_counter.clone();
-            let passed_counter = passed_counter.clone();
-            
+        let rules = self.rules.clone();
+        let alert_sender = self.alert_sender.clone();
+
+        let processed_stream = stream.filter_map(move |mut event| {
+            let rules = rules.clone();
+            let alert_sender = alert_sender.clone();
+
             async move {
-                // Increment total events processed
-                total_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                
-                // Check if this is an HTTP parser event and should be filtered
-                let should_filter = if filters.is_empty() {
-                    false
-                } else if event.source != "http_parser" {
-                    false
+                let start = Instant::now();
+
+                let matched = if rules.is_empty() || event.source != "http_parser" {
+                    None
                 } else {
-                    // Evaluate each filter expression
-                    let mut filtered = false;
-                    for filter in &filters {
-                        if filter.evaluate(&event.data) {
-                            if debug {
-                                eprintln!("[HTTPFilter DEBUG] Event filtered by: {}", filter.expression);
+                    rules.iter().find(|rule| rule.expression.evaluate(&event.data))
+                };
+
+                let Some(rule) = matched else {
+                    record_analyzer_process("HTTPFilter", AnalyzerOutcome::Passed, start.elapsed());
+                    return Some(event);
+                };
+
+                record_filter_match("HTTPFilter", &rule.expression.expression);
+                let diagnostic = RuleDiagnostic {
+                    rule_id: rule.id.clone(),
+                    severity: rule.severity,
+                    expression: rule.expression.expression.clone(),
+                    event_source: event.source.clone(),
+                };
+                log_diagnostic(&diagnostic);
+
+                match &rule.action {
+                    RuleAction::Drop => {
+                        record_analyzer_process("HTTPFilter", AnalyzerOutcome::Filtered, start.elapsed());
+                        None
+                    }
+                    RuleAction::Pass => {
+                        record_analyzer_process("HTTPFilter", AnalyzerOutcome::Passed, start.elapsed());
+                        Some(event)
+                    }
+                    RuleAction::Redact(field) => {
+                        if let Some(obj) = event.data.as_object_mut() {
+                            obj.insert(field.clone(), Value::Null);
+                        }
+                        record_analyzer_process("HTTPFilter", AnalyzerOutcome::Passed, start.elapsed());
+                        Some(event)
+                    }
+                    RuleAction::Tag(name) => {
+                        if let Some(obj) = event.data.as_object_mut() {
+                            obj.insert("http_filter_tag".to_string(), Value::String(name.clone()));
+                        }
+                        record_analyzer_process("HTTPFilter", AnalyzerOutcome::Passed, start.elapsed());
+                        Some(event)
+                    }
+                    RuleAction::Alert => {
+                        if let Some(sender) = &alert_sender {
+                            let alerted = AlertedEvent { event: event.clone(), diagnostic: diagnostic.clone() };
+                            if let Err(mpsc::error::TrySendError::Full(_)) = sender.try_send(alerted) {
+                                alert_dropped_counter().fetch_add(1, Ordering::Relaxed);
                             }
-                            filtered = true;
-                            break;
                         }
+                        record_analyzer_process("HTTPFilter", AnalyzerOutcome::Passed, start.elapsed());
+                        Some(event)
                     }
-                    filtered
-                };
-
-                if should_filter {
-                    // Increment filtered counter
-                    filtered_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                    // Update global metrics
-                    let total = total_counter.load(std::sync::atomic::Ordering::Relaxed);
-                    let filtered = filtered_counter.load(std::sync::atomic::Ordering::Relaxed);
-                    let passed = passed_counter.load(std::sync::atomic::Ordering::Relaxed);
-                    update_global_metrics(total, filtered, passed);
-                    None // Filter out
-                } else {
-                    // Increment passed counter  
-                    passed_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                    // Update global metrics
-                    let total = total_counter.load(std::sync::atomic::Ordering::Relaxed);
-                    let filtered = filtered_counter.load(std::sync::atomic::Ordering::Relaxed);
-                    let passed = passed_counter.load(std::sync::atomic::Ordering::Relaxed);
-                    update_global_metrics(total, filtered, passed);
-                    Some(event) // Pass through
                 }
             }
         });
 
-        Ok(Box::pin(filtered_stream))
+        Ok(Box::pin(processed_stream))
     }
 
     fn name(&self) -> &str {
@@ -450,9 +875,9 @@ mod tests {
 
     #[test]
     fn test_filter_expression_parsing() {
-        let expr = FilterExpression::parse("request.path=/health");
+        let expr = FilterExpression::parse("request.path=/health").unwrap();
         match expr.parsed {
-            FilterNode::Condition { target, field, operator, value } => {
+            FilterNode::Condition { target, field, operator, value, .. } => {
                 assert_eq!(target, "request");
                 assert_eq!(field, "path");
                 assert_eq!(operator, "exact");
@@ -464,74 +889,274 @@ mod tests {
 
     #[test]
     fn test_request_filtering() {
-        let filter = FilterExpression::parse("request.method=GET");
-        
+        let filter = FilterExpression::parse("request.method=GET").unwrap();
+
         let request_data = json!({
             "message_type": "request",
             "method": "GET",
             "path": "/api/test",
             "headers": {}
         });
-        
+
         assert!(filter.evaluate(&request_data));
-        
+
         let post_data = json!({
             "message_type": "request",
             "method": "POST",
             "path": "/api/test",
             "headers": {}
         });
-        
+
         assert!(!filter.evaluate(&post_data));
     }
 
     #[test]
     fn test_response_filtering() {
-        let filter = FilterExpression::parse("response.status_code=404");
-        
+        let filter = FilterExpression::parse("response.status_code=404").unwrap();
+
         let response_data = json!({
             "message_type": "response",
             "status_code": 404,
             "status_text": "Not Found",
             "headers": {}
         });
-        
+
         assert!(filter.evaluate(&response_data));
-        
+
         let ok_data = json!({
             "message_type": "response",
             "status_code": 200,
             "status_text": "OK",
             "headers": {}
         });
-        
+
         assert!(!filter.evaluate(&ok_data));
     }
 
     #[test]
     fn test_complex_expressions() {
-        let filter = FilterExpression::parse("request.method=GET | response.status_code=404");
-        
+        let filter = FilterExpression::parse("request.method=GET | response.status_code=404").unwrap();
+
         let get_request = json!({
             "message_type": "request",
             "method": "GET",
             "path": "/api/test"
         });
-        
+
         let not_found_response = json!({
             "message_type": "response",
             "status_code": 404
         });
-        
+
         let post_request = json!({
-            "message_type": "request", 
+            "message_type": "request",
             "method": "POST",
             "path": "/api/test"
         });
-        
+
         assert!(filter.evaluate(&get_request));
         assert!(filter.evaluate(&not_found_response));
         assert!(!filter.evaluate(&post_request));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_not_negates_a_grouped_expression() {
+        let filter = FilterExpression::parse("!(request.method=GET & response.status_code=404) | request.path_contains=/admin").unwrap();
+
+        let get_404 = json!({ "message_type": "request", "method": "GET", "path": "/api/test" });
+        let admin_path = json!({ "message_type": "request", "method": "POST", "path": "/admin/users" });
+        let other = json!({ "message_type": "request", "method": "POST", "path": "/api/test" });
+
+        // Not(And(...)) never sees both halves of the And true in the same
+        // evaluate() call against a single JSON object (And needs both
+        // request.method and response.status_code on the same event), so it
+        // reduces to "not a GET request", which `other`/`admin_path` satisfy
+        // because they're POST.
+        assert!(filter.evaluate(&admin_path));
+        assert!(filter.evaluate(&other));
+        assert!(!filter.evaluate(&get_404));
+    }
+
+    #[test]
+    fn test_not_equal_operator() {
+        let filter = FilterExpression::parse("request.method!=GET").unwrap();
+
+        assert!(!filter.evaluate(&json!({ "message_type": "request", "method": "GET" })));
+        assert!(filter.evaluate(&json!({ "message_type": "request", "method": "POST" })));
+    }
+
+    #[test]
+    fn test_regex_operator_compiles_and_matches() {
+        let filter = FilterExpression::parse(r#"request.path=~^/api/v[0-9]+/users$"#).unwrap();
+
+        assert!(filter.evaluate(&json!({ "message_type": "request", "path": "/api/v2/users" })));
+        assert!(!filter.evaluate(&json!({ "message_type": "request", "path": "/api/users" })));
+    }
+
+    #[test]
+    fn test_invalid_regex_is_a_parse_error() {
+        let err = FilterExpression::parse("request.path=~(unclosed").unwrap_err();
+        assert!(matches!(err.reason(), FilterParseErrorReason::InvalidRegex(_)));
+    }
+
+    #[test]
+    fn test_numeric_comparison_operators() {
+        let filter = FilterExpression::parse("response.status_code>=500").unwrap();
+
+        assert!(filter.evaluate(&json!({ "message_type": "response", "status_code": 503 })));
+        assert!(!filter.evaluate(&json!({ "message_type": "response", "status_code": 404 })));
+    }
+
+    #[test]
+    fn test_numeric_comparison_fails_closed_on_non_numeric_value() {
+        let filter = FilterExpression::parse("response.status_code>not-a-number").unwrap();
+        assert!(!filter.evaluate(&json!({ "message_type": "response", "status_code": 503 })));
+    }
+
+    #[test]
+    fn test_quoted_value_protects_special_characters() {
+        let filter = FilterExpression::parse(r#"request.path="/a&b|c(d)!e""#).unwrap();
+        assert!(filter.evaluate(&json!({ "message_type": "request", "path": "/a&b|c(d)!e" })));
+    }
+
+    #[test]
+    fn test_parse_rejects_unterminated_parenthesis() {
+        let err = FilterExpression::parse("(request.method=GET & response.status_code=404").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::UnterminatedParenthesis);
+        assert_eq!(err.offset(), 0);
+    }
+
+    #[test]
+    fn test_parse_rejects_unterminated_quote() {
+        let err = FilterExpression::parse(r#"request.path="/admin"#).unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::UnterminatedQuote);
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_operand() {
+        let err = FilterExpression::parse("request.method=GET &").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::EmptyOperand);
+    }
+
+    fn http_event(message_type: &str, extra: Value) -> Event {
+        let mut data = json!({ "message_type": message_type });
+        if let (Some(obj), Some(extra_obj)) = (data.as_object_mut(), extra.as_object()) {
+            for (k, v) in extra_obj {
+                obj.insert(k.clone(), v.clone());
+            }
+        }
+        Event::new("http_parser".to_string(), 1, "http".to_string(), data)
+    }
+
+    fn input_stream(events: Vec<Event>) -> EventStream {
+        Box::pin(futures::stream::iter(events))
+    }
+
+    async fn collect(stream: EventStream) -> Vec<Event> {
+        stream.collect().await
+    }
+
+    #[tokio::test]
+    async fn test_first_match_wins_over_later_rules() {
+        let rules = vec![
+            Rule::new("allow-health", FilterExpression::parse("request.path=/health").unwrap(), RuleSeverity::Info, RuleAction::Pass),
+            Rule::new("drop-all", FilterExpression::parse("request.path_prefix=/").unwrap(), RuleSeverity::Warning, RuleAction::Drop),
+        ];
+        let mut filter = HTTPFilter::with_rules(rules);
+
+        let events = vec![
+            http_event("request", json!({ "method": "GET", "path": "/health" })),
+            http_event("request", json!({ "method": "GET", "path": "/api/test" })),
+        ];
+
+        let out = collect(filter.process(input_stream(events)).await.unwrap()).await;
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].data["path"], "/health");
+    }
+
+    #[tokio::test]
+    async fn test_redact_action_nulls_the_field() {
+        let rules = vec![Rule::new(
+            "redact-body",
+            FilterExpression::parse("request.path=/secret").unwrap(),
+            RuleSeverity::Warning,
+            RuleAction::Redact("body".to_string()),
+        )];
+        let mut filter = HTTPFilter::with_rules(rules);
+
+        let events = vec![http_event("request", json!({ "method": "POST", "path": "/secret", "body": "token=abc" }))];
+        let out = collect(filter.process(input_stream(events)).await.unwrap()).await;
+
+        assert_eq!(out.len(), 1);
+        assert!(out[0].data["body"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_tag_action_annotates_event_data() {
+        let rules = vec![Rule::new(
+            "tag-admin",
+            FilterExpression::parse("request.path_prefix=/admin").unwrap(),
+            RuleSeverity::Info,
+            RuleAction::Tag("admin-route".to_string()),
+        )];
+        let mut filter = HTTPFilter::with_rules(rules);
+
+        let events = vec![http_event("request", json!({ "method": "GET", "path": "/admin/users" }))];
+        let out = collect(filter.process(input_stream(events)).await.unwrap()).await;
+
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].data["http_filter_tag"], "admin-route");
+    }
+
+    #[tokio::test]
+    async fn test_alert_action_passes_through_and_forwards_a_copy() {
+        let rules = vec![Rule::new(
+            "alert-5xx",
+            FilterExpression::parse("response.status_code=500").unwrap(),
+            RuleSeverity::Error,
+            RuleAction::Alert,
+        )];
+        let (mut filter, mut alerts) = HTTPFilter::with_rules(rules).with_alert_sink(8);
+
+        let events = vec![http_event("response", json!({ "status_code": 500 }))];
+        let out = collect(filter.process(input_stream(events)).await.unwrap()).await;
+
+        assert_eq!(out.len(), 1, "Alert still passes the event through");
+
+        let alerted = alerts.try_recv().expect("alert sink should have received a copy");
+        assert_eq!(alerted.diagnostic.rule_id, "alert-5xx");
+        assert_eq!(alerted.diagnostic.severity, RuleSeverity::Error);
+        assert_eq!(alerted.event.data["status_code"], 500);
+    }
+
+    #[tokio::test]
+    async fn test_with_patterns_still_drops_by_default() {
+        let mut filter = HTTPFilter::with_patterns(vec!["path_contains=/admin".to_string()]).unwrap();
+
+        let events = vec![
+            http_event("request", json!({ "method": "GET", "path": "/admin/users" })),
+            http_event("request", json!({ "method": "GET", "path": "/api/test" })),
+        ];
+        let out = collect(filter.process(input_stream(events)).await.unwrap()).await;
+
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].data["path"], "/api/test");
+    }
+
+    #[tokio::test]
+    async fn test_with_patterns_surfaces_parse_error() {
+        let err = HTTPFilter::with_patterns(vec!["request.path=~(unclosed".to_string()]).unwrap_err();
+        assert!(matches!(err.reason(), FilterParseErrorReason::InvalidRegex(_)));
+    }
+
+    #[tokio::test]
+    async fn test_non_http_parser_events_bypass_all_rules() {
+        let rules = vec![Rule::new("drop-all", FilterExpression::parse("request.path_prefix=/").unwrap(), RuleSeverity::Warning, RuleAction::Drop)];
+        let mut filter = HTTPFilter::with_rules(rules);
+
+        let event = Event::new("ssl".to_string(), 1, "ssl".to_string(), json!({ "message_type": "request", "path": "/anything" }));
+        let out = collect(filter.process(input_stream(vec![event])).await.unwrap()).await;
+
+        assert_eq!(out.len(), 1);
+    }
+}
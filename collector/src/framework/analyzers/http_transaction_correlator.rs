@@ -0,0 +1,219 @@
+use super::{Analyzer, AnalyzerError};
+use crate::framework::runners::EventStream;
+use crate::framework::core::Event;
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Default timeout for an unmatched request: how long it waits for its
+/// response before the correlator gives up on it.
+const DEFAULT_TIMEOUT_NS: u64 = 30_000_000_000;
+
+/// Pairs `HTTPParser`'s per-message `request`/`response` events for the
+/// same connection into a synthesized `http_transaction` event carrying
+/// both sides plus the latency between them, so downstream consumers don't
+/// have to reassemble request/response pairs themselves. Original events
+/// pass through unchanged alongside the synthesized ones.
+pub struct HTTPTransactionCorrelator {
+    /// Outstanding requests per connection, oldest first, awaiting a
+    /// matching response.
+    pending: Arc<Mutex<HashMap<String, VecDeque<PendingRequest>>>>,
+    /// How long an unmatched request may sit in the queue before it's
+    /// evicted instead of held onto forever.
+    timeout_ns: u64,
+}
+
+/// A buffered request awaiting the response that completes it.
+struct PendingRequest {
+    method: Option<String>,
+    path: Option<String>,
+    protocol: Option<String>,
+    headers: serde_json::Value,
+    timestamp: u64,
+}
+
+impl HTTPTransactionCorrelator {
+    /// Create a new correlator with the default 30-second unmatched-request timeout
+    pub fn new() -> Self {
+        Self::new_with_timeout(DEFAULT_TIMEOUT_NS)
+    }
+
+    /// Create a new correlator with a custom unmatched-request timeout (in nanoseconds)
+    pub fn new_with_timeout(timeout_ns: u64) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            timeout_ns,
+        }
+    }
+
+    /// Connection key matching the `"{pid}:{tid}"` convention `SSEProcessor`
+    /// and `HTTPParser` use for the same purpose.
+    fn connection_key(event: &Event) -> String {
+        let tid = event.data.get("tid").and_then(|v| v.as_u64()).unwrap_or(0);
+        format!("{}:{}", event.pid, tid)
+    }
+
+    /// Drop every request that's been waiting longer than `timeout_ns`,
+    /// across all connections - not just the one the current event belongs
+    /// to. A dropped connection by definition never sends another event on
+    /// its own key, so sweeping only that key (as the old `"response"`-only
+    /// check did) would leave its queue and `HashMap` entry behind for the
+    /// life of the process; sweeping every connection on every `http_parser`
+    /// event instead means unrelated traffic elsewhere is enough to keep the
+    /// whole map clean.
+    fn evict_stale(
+        pending: &mut HashMap<String, VecDeque<PendingRequest>>,
+        now: u64,
+        timeout_ns: u64,
+    ) {
+        pending.retain(|_key, queue| {
+            while let Some(front) = queue.front() {
+                if now.saturating_sub(front.timestamp) > timeout_ns {
+                    queue.pop_front();
+                } else {
+                    break;
+                }
+            }
+            !queue.is_empty()
+        });
+    }
+
+    /// Feed one `http_parser` event into the pending-request tracker,
+    /// returning a synthesized transaction event once a response matches
+    /// the oldest outstanding request on its connection.
+    fn correlate(
+        event: &Event,
+        pending: &Arc<Mutex<HashMap<String, VecDeque<PendingRequest>>>>,
+        timeout_ns: u64,
+    ) -> Option<Event> {
+        let message_type = event.data.get("message_type").and_then(|v| v.as_str()).unwrap_or("");
+        let key = Self::connection_key(event);
+        let mut pending = pending.lock().unwrap();
+
+        Self::evict_stale(&mut pending, event.timestamp, timeout_ns);
+
+        match message_type {
+            "request" => {
+                pending.entry(key).or_default().push_back(PendingRequest {
+                    method: event.data.get("method").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    path: event.data.get("path").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    protocol: event.data.get("protocol").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    headers: event.data.get("headers").cloned().unwrap_or_else(|| json!({})),
+                    timestamp: event.timestamp,
+                });
+                None
+            }
+            "response" => {
+                let queue = pending.get_mut(&key)?;
+                let request = queue.pop_front()?;
+                if queue.is_empty() {
+                    pending.remove(&key);
+                }
+
+                let duration_ms = event.timestamp.saturating_sub(request.timestamp) as f64 / 1_000_000.0;
+
+                let payload = json!({
+                    "method": request.method,
+                    "path": request.path,
+                    "protocol": request.protocol,
+                    "request_headers": request.headers,
+                    "status_code": event.data.get("status_code"),
+                    "status_text": event.data.get("status_text"),
+                    "response_headers": event.data.get("headers"),
+                    "duration_ms": duration_ms,
+                });
+
+                Some(Event::new(
+                    "http_transaction".to_string(),
+                    event.pid,
+                    "http_transaction".to_string(),
+                    payload,
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for HTTPTransactionCorrelator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Analyzer for HTTPTransactionCorrelator {
+    async fn process(&mut self, stream: EventStream) -> Result<EventStream, AnalyzerError> {
+        let pending = Arc::clone(&self.pending);
+        let timeout_ns = self.timeout_ns;
+
+        let processed_stream = async_stream::stream! {
+            let mut stream = stream;
+            while let Some(event) = stream.next().await {
+                if event.source != "http_parser" {
+                    yield event;
+                    continue;
+                }
+
+                let transaction = Self::correlate(&event, &pending, timeout_ns);
+                yield event;
+                if let Some(transaction) = transaction {
+                    yield transaction;
+                }
+            }
+        };
+
+        Ok(Box::pin(processed_stream))
+    }
+
+    fn name(&self) -> &str {
+        "HTTPTransactionCorrelator"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::core::Event;
+
+    fn http_parser_event(message_type: &str, pid: u32, tid: u64, timestamp: u64) -> Event {
+        Event::new_with_timestamp(
+            timestamp,
+            "http_parser".to_string(),
+            pid,
+            "test_comm".to_string(),
+            json!({
+                "message_type": message_type,
+                "tid": tid,
+                "method": "GET",
+                "path": "/",
+                "protocol": "HTTP/1.1",
+            }),
+        )
+    }
+
+    #[test]
+    fn test_stale_request_on_dropped_connection_is_evicted_by_unrelated_traffic() {
+        let pending: Arc<Mutex<HashMap<String, VecDeque<PendingRequest>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let timeout_ns = 1_000;
+
+        // A request arrives on connection 1:1 and then the connection is
+        // dropped - no response ever follows on that key.
+        let dropped_request = http_parser_event("request", 1, 1, 0);
+        assert!(HTTPTransactionCorrelator::correlate(&dropped_request, &pending, timeout_ns).is_none());
+        assert!(pending.lock().unwrap().contains_key("1:1"));
+
+        // Unrelated traffic on a different connection, well past the
+        // timeout, should sweep the dropped connection's entry even though
+        // it never receives a response of its own.
+        let unrelated_request = http_parser_event("request", 2, 1, timeout_ns + 1);
+        assert!(HTTPTransactionCorrelator::correlate(&unrelated_request, &pending, timeout_ns).is_none());
+
+        let pending = pending.lock().unwrap();
+        assert!(!pending.contains_key("1:1"), "dropped connection's pending entry should have been evicted");
+        assert!(pending.contains_key("2:1"));
+    }
+}
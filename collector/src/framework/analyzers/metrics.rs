@@ -0,0 +1,348 @@
+/// Global, Prometheus-style metrics collector.
+///
+/// Tallies into a single process-wide `OnceLock`, the same way
+/// [`SSLFilter`](super::SSLFilter)/[`HTTPFilter`](super::HTTPFilter) publish
+/// their own counters, rather than per-instance state: a command only ever
+/// runs one event pipeline per process, so there is nothing to disambiguate
+/// between and a global makes the counters reachable from a free function
+/// that doesn't need a handle to the analyzer chain.
+///
+/// There is currently no `/metrics` HTTP route serving this text -
+/// `server::web::WebServer` in this checkout has no route table to add one
+/// to (see the similar TLS-support gap noted in
+/// `main.rs::start_web_server_if_enabled`). Until that module exists, the
+/// rendered text is surfaced the same way the existing filter metrics are:
+/// printed on shutdown by [`print_global_prometheus_metrics`].
+use super::{Analyzer, AnalyzerError};
+use crate::framework::runners::EventStream;
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Latest CPU/memory reading reported for a (pid, comm) pair.
+#[derive(Debug, Clone, Default)]
+struct ProcessGauges {
+    cpu_percent: f64,
+    memory_rss_mb: u64,
+}
+
+/// Upper bounds (in microseconds) of the latency histogram buckets tallied
+/// per analyzer by [`record_analyzer_process`]. A fixed ladder, matching
+/// Prometheus's own convention, rather than a dynamically sized one - these
+/// cover the sub-millisecond-to-few-milliseconds range a single `process()`
+/// call over one event is expected to take.
+const LATENCY_BUCKETS_US: [f64; 7] = [10.0, 50.0, 100.0, 500.0, 1_000.0, 5_000.0, 10_000.0];
+
+/// Outcome of a single event passed through `Analyzer::process`, tallied by
+/// [`record_analyzer_process`] into [`AnalyzerCounters::passed`]/`filtered`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyzerOutcome {
+    /// The event was forwarded downstream.
+    Passed,
+    /// The event was dropped (e.g. matched an exclude filter).
+    Filtered,
+}
+
+/// Per-analyzer event counters and process-latency histogram, keyed by
+/// `Analyzer::name()` so every stage in the pipeline - not just
+/// `HTTPFilter`/`SSLFilter` - contributes its own labeled series to
+/// [`render_global_prometheus_metrics`].
+#[derive(Debug, Default)]
+struct AnalyzerCounters {
+    passed: u64,
+    filtered: u64,
+    /// Cumulative counts, i.e. `latency_bucket_counts[i]` is the number of
+    /// observations `<= LATENCY_BUCKETS_US[i]`, matching Prometheus's own
+    /// histogram bucket semantics.
+    latency_bucket_counts: [u64; LATENCY_BUCKETS_US.len()],
+    latency_sum_us: f64,
+    latency_count: u64,
+}
+
+#[derive(Debug, Default)]
+struct MetricsState {
+    /// Total events seen, keyed by `Event::source` (e.g. "ssl", "system").
+    events_processed: HashMap<String, u64>,
+    /// Latest CPU/memory sample per (pid, comm) reported by `SystemRunner`.
+    process_gauges: HashMap<(u32, String), ProcessGauges>,
+    /// Count of `system` events whose CPU/memory crossed a configured
+    /// threshold, keyed by (pid, comm).
+    threshold_breaches: HashMap<(u32, String), u64>,
+    /// Per-analyzer event/latency counters, keyed by `Analyzer::name()`.
+    analyzer_counters: HashMap<String, AnalyzerCounters>,
+    /// Per-(analyzer, filter expression) match counts, so a user can see
+    /// which rule is responsible for the most dropped events.
+    filter_match_counts: HashMap<(String, String), u64>,
+}
+
+static METRICS_GLOBAL_STATE: OnceLock<Arc<Mutex<MetricsState>>> = OnceLock::new();
+
+fn global_state() -> &'static Arc<Mutex<MetricsState>> {
+    METRICS_GLOBAL_STATE.get_or_init(|| Arc::new(Mutex::new(MetricsState::default())))
+}
+
+/// Global analyzer that tallies events-processed/CPU/memory/threshold-breach
+/// counters from the merged event stream without altering it. Add it to a
+/// runner's (or `AgentRunner`'s global) analyzer chain the same way
+/// `OutputAnalyzer`/`FileLogger` are added; the counters it tallies are read
+/// back via [`render_global_prometheus_metrics`].
+#[derive(Debug, Default)]
+pub struct MetricsCollector;
+
+impl MetricsCollector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Analyzer for MetricsCollector {
+    async fn process(&mut self, stream: EventStream) -> Result<EventStream, AnalyzerError> {
+        let processed_stream = stream.map(move |event| {
+            if let Ok(mut state) = global_state().lock() {
+                *state.events_processed.entry(event.source.clone()).or_insert(0) += 1;
+
+                if event.source == "system" {
+                    let cpu_percent = event
+                        .data
+                        .get("cpu")
+                        .and_then(|c| c.get("percent"))
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.parse::<f64>().ok())
+                        .unwrap_or(0.0);
+                    let memory_rss_mb = event
+                        .data
+                        .get("memory")
+                        .and_then(|m| m.get("rss_mb"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0);
+
+                    let key = (event.pid, event.comm.clone());
+                    state.process_gauges.insert(
+                        key.clone(),
+                        ProcessGauges { cpu_percent, memory_rss_mb },
+                    );
+
+                    if event.data.get("alert").and_then(|v| v.as_bool()).unwrap_or(false) {
+                        *state.threshold_breaches.entry(key).or_insert(0) += 1;
+                    }
+                }
+            }
+            event
+        });
+
+        Ok(Box::pin(processed_stream))
+    }
+
+    fn name(&self) -> &str {
+        "MetricsCollector"
+    }
+}
+
+/// Tally one `Analyzer::process` outcome for `analyzer` (its `name()`),
+/// along with how long that single event took to evaluate, into the
+/// crate-wide registry rendered by [`render_global_prometheus_metrics`].
+/// Any `Analyzer` can call this from inside its `process()` stream, not
+/// just `HTTPFilter`/`SSLFilter`.
+pub fn record_analyzer_process(analyzer: &str, outcome: AnalyzerOutcome, elapsed: Duration) {
+    let mut state = match global_state().lock() {
+        Ok(state) => state,
+        Err(_) => return,
+    };
+
+    let counters = state.analyzer_counters.entry(analyzer.to_string()).or_default();
+    match outcome {
+        AnalyzerOutcome::Passed => counters.passed += 1,
+        AnalyzerOutcome::Filtered => counters.filtered += 1,
+    }
+
+    let elapsed_us = elapsed.as_secs_f64() * 1_000_000.0;
+    counters.latency_count += 1;
+    counters.latency_sum_us += elapsed_us;
+    for (bucket_count, bound) in counters.latency_bucket_counts.iter_mut().zip(LATENCY_BUCKETS_US.iter()) {
+        if elapsed_us <= *bound {
+            *bucket_count += 1;
+        }
+    }
+}
+
+/// Record that `expression` - one of `analyzer`'s configured filter rules -
+/// matched and dropped an event, so [`render_global_prometheus_metrics`]
+/// can show which rule is responsible for the most drops.
+pub fn record_filter_match(analyzer: &str, expression: &str) {
+    if let Ok(mut state) = global_state().lock() {
+        *state.filter_match_counts.entry((analyzer.to_string(), expression.to_string())).or_insert(0) += 1;
+    }
+}
+
+/// Render every counter/gauge tallied by [`MetricsCollector`] (plus
+/// `FileLogger`'s bytes-written/rotation-count totals, see
+/// [`super::file_logger::global_file_logger_metrics`]) as Prometheus text
+/// exposition format.
+pub fn render_global_prometheus_metrics() -> String {
+    let state = global_state().lock().unwrap();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# HELP agentsight_events_processed_total Total events processed, by runner/source type.");
+    let _ = writeln!(out, "# TYPE agentsight_events_processed_total counter");
+    for (source, count) in &state.events_processed {
+        let _ = writeln!(out, "agentsight_events_processed_total{{source=\"{}\"}} {}", source, count);
+    }
+
+    let _ = writeln!(out, "# HELP agentsight_process_cpu_percent Latest reported CPU usage percent, by pid/comm.");
+    let _ = writeln!(out, "# TYPE agentsight_process_cpu_percent gauge");
+    for ((pid, comm), gauges) in &state.process_gauges {
+        let _ = writeln!(out, "agentsight_process_cpu_percent{{pid=\"{}\",comm=\"{}\"}} {}", pid, comm, gauges.cpu_percent);
+    }
+
+    let _ = writeln!(out, "# HELP agentsight_process_memory_rss_mb Latest reported resident memory in MB, by pid/comm.");
+    let _ = writeln!(out, "# TYPE agentsight_process_memory_rss_mb gauge");
+    for ((pid, comm), gauges) in &state.process_gauges {
+        let _ = writeln!(out, "agentsight_process_memory_rss_mb{{pid=\"{}\",comm=\"{}\"}} {}", pid, comm, gauges.memory_rss_mb);
+    }
+
+    let _ = writeln!(out, "# HELP agentsight_threshold_breaches_total Count of system samples that crossed a configured CPU/memory threshold, by pid/comm.");
+    let _ = writeln!(out, "# TYPE agentsight_threshold_breaches_total counter");
+    for ((pid, comm), count) in &state.threshold_breaches {
+        let _ = writeln!(out, "agentsight_threshold_breaches_total{{pid=\"{}\",comm=\"{}\"}} {}", pid, comm, count);
+    }
+
+    let _ = writeln!(out, "# HELP agentsight_analyzer_events_total Events seen by each analyzer pipeline stage, by outcome.");
+    let _ = writeln!(out, "# TYPE agentsight_analyzer_events_total counter");
+    for (analyzer, counters) in &state.analyzer_counters {
+        let _ = writeln!(out, "agentsight_analyzer_events_total{{analyzer=\"{}\",outcome=\"passed\"}} {}", analyzer, counters.passed);
+        let _ = writeln!(out, "agentsight_analyzer_events_total{{analyzer=\"{}\",outcome=\"filtered\"}} {}", analyzer, counters.filtered);
+    }
+
+    let _ = writeln!(out, "# HELP agentsight_analyzer_process_duration_microseconds Per-event Analyzer::process latency.");
+    let _ = writeln!(out, "# TYPE agentsight_analyzer_process_duration_microseconds histogram");
+    for (analyzer, counters) in &state.analyzer_counters {
+        for (bound, count) in LATENCY_BUCKETS_US.iter().zip(counters.latency_bucket_counts.iter()) {
+            let _ = writeln!(out, "agentsight_analyzer_process_duration_microseconds_bucket{{analyzer=\"{}\",le=\"{}\"}} {}", analyzer, bound, count);
+        }
+        let _ = writeln!(out, "agentsight_analyzer_process_duration_microseconds_bucket{{analyzer=\"{}\",le=\"+Inf\"}} {}", analyzer, counters.latency_count);
+        let _ = writeln!(out, "agentsight_analyzer_process_duration_microseconds_sum{{analyzer=\"{}\"}} {}", analyzer, counters.latency_sum_us);
+        let _ = writeln!(out, "agentsight_analyzer_process_duration_microseconds_count{{analyzer=\"{}\"}} {}", analyzer, counters.latency_count);
+    }
+
+    let _ = writeln!(out, "# HELP agentsight_filter_match_total Events dropped by each configured filter expression, by analyzer.");
+    let _ = writeln!(out, "# TYPE agentsight_filter_match_total counter");
+    for ((analyzer, expression), count) in &state.filter_match_counts {
+        let _ = writeln!(out, "agentsight_filter_match_total{{analyzer=\"{}\",expression=\"{}\"}} {}", analyzer, expression, count);
+    }
+
+    let (bytes_written, rotation_count) = super::file_logger::global_file_logger_metrics();
+    let _ = writeln!(out, "# HELP agentsight_file_logger_bytes_written_total Total bytes written to rotating/plain log files.");
+    let _ = writeln!(out, "# TYPE agentsight_file_logger_bytes_written_total counter");
+    let _ = writeln!(out, "agentsight_file_logger_bytes_written_total {}", bytes_written);
+    let _ = writeln!(out, "# HELP agentsight_file_logger_rotations_total Total log rotations performed.");
+    let _ = writeln!(out, "# TYPE agentsight_file_logger_rotations_total counter");
+    let _ = writeln!(out, "agentsight_file_logger_rotations_total {}", rotation_count);
+
+    out
+}
+
+/// Print the collected metrics in Prometheus text format, alongside
+/// `print_global_http_filter_metrics`/`print_global_ssl_filter_metrics` on
+/// shutdown.
+pub fn print_global_prometheus_metrics() {
+    print!("{}", render_global_prometheus_metrics());
+}
+
+/// Latest (cpu_percent, memory_rss_mb) sample tallied for `pid`, if
+/// `MetricsCollector` has seen a `system` event for it yet. Used by
+/// `server::graphql`'s `systemStats(pid)` query so it doesn't need to
+/// duplicate `SystemRunner`'s gauge tracking.
+pub fn system_stats_for_pid(pid: u32) -> Option<(f64, u64)> {
+    let state = global_state().lock().ok()?;
+    state
+        .process_gauges
+        .iter()
+        .find(|((gauge_pid, _), _)| *gauge_pid == pid)
+        .map(|(_, gauges)| (gauges.cpu_percent, gauges.memory_rss_mb))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::core::Event;
+    use futures::stream;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_collector_counts_events_by_source() {
+        // Reset isn't possible on the process-wide global, so assert on the
+        // delta rather than an absolute count.
+        let before = global_state().lock().unwrap().events_processed.get("metrics_test_source").copied().unwrap_or(0);
+
+        let mut collector = MetricsCollector::new();
+        let events = vec![
+            Event::new("metrics_test_source".to_string(), 1, "proc".to_string(), json!({})),
+            Event::new("metrics_test_source".to_string(), 1, "proc".to_string(), json!({})),
+        ];
+        let input_stream: EventStream = Box::pin(stream::iter(events));
+        let output_stream = collector.process(input_stream).await.unwrap();
+        let collected: Vec<_> = output_stream.collect().await;
+        assert_eq!(collected.len(), 2);
+
+        let after = global_state().lock().unwrap().events_processed.get("metrics_test_source").copied().unwrap_or(0);
+        assert_eq!(after - before, 2);
+    }
+
+    #[tokio::test]
+    async fn test_collector_tracks_system_gauges_and_breaches() {
+        let mut collector = MetricsCollector::new();
+        let event = Event::new(
+            "system".to_string(),
+            424242,
+            "metrics-test-proc".to_string(),
+            json!({
+                "cpu": {"percent": "12.50"},
+                "memory": {"rss_mb": 77},
+                "alert": true,
+            }),
+        );
+        let input_stream: EventStream = Box::pin(stream::iter(vec![event]));
+        let output_stream = collector.process(input_stream).await.unwrap();
+        let _: Vec<_> = output_stream.collect().await;
+
+        let rendered = render_global_prometheus_metrics();
+        assert!(rendered.contains("pid=\"424242\",comm=\"metrics-test-proc\"} 12.5"));
+        assert!(rendered.contains("agentsight_threshold_breaches_total{pid=\"424242\",comm=\"metrics-test-proc\"} 1"));
+    }
+
+    #[test]
+    fn test_record_analyzer_process_tallies_passed_and_filtered() {
+        record_analyzer_process("metrics_test_analyzer", AnalyzerOutcome::Passed, Duration::from_micros(5));
+        record_analyzer_process("metrics_test_analyzer", AnalyzerOutcome::Filtered, Duration::from_micros(5));
+
+        let rendered = render_global_prometheus_metrics();
+        assert!(rendered.contains("agentsight_analyzer_events_total{analyzer=\"metrics_test_analyzer\",outcome=\"passed\"}"));
+        assert!(rendered.contains("agentsight_analyzer_events_total{analyzer=\"metrics_test_analyzer\",outcome=\"filtered\"}"));
+    }
+
+    #[test]
+    fn test_record_analyzer_process_latency_lands_in_every_bucket_at_or_above_it() {
+        record_analyzer_process("metrics_test_latency_analyzer", AnalyzerOutcome::Passed, Duration::from_micros(60));
+
+        let state = global_state().lock().unwrap();
+        let counters = &state.analyzer_counters["metrics_test_latency_analyzer"];
+        // 60us is above the 10/50us buckets but at or below everything from 100us up.
+        assert_eq!(counters.latency_bucket_counts[0], 0); // le=10
+        assert_eq!(counters.latency_bucket_counts[1], 0); // le=50
+        assert_eq!(counters.latency_bucket_counts[2], 1); // le=100
+        assert_eq!(counters.latency_count, 1);
+    }
+
+    #[test]
+    fn test_record_filter_match_is_keyed_by_analyzer_and_expression() {
+        record_filter_match("metrics_test_filter_analyzer", "data~chunked");
+        record_filter_match("metrics_test_filter_analyzer", "data~chunked");
+
+        let rendered = render_global_prometheus_metrics();
+        assert!(rendered.contains("agentsight_filter_match_total{analyzer=\"metrics_test_filter_analyzer\",expression=\"data~chunked\"} 2"));
+    }
+}
@@ -9,10 +9,19 @@ pub type AnalyzerError = Box<dyn std::error::Error + Send + Sync>;
 pub trait Analyzer: Send + Sync {
     /// Process an event stream and return a processed stream
     async fn process(&mut self, stream: EventStream) -> Result<EventStream, AnalyzerError>;
-    
+
     /// Get the name of this analyzer
     #[allow(dead_code)]
     fn name(&self) -> &str;
+
+    /// Flush any buffered state before shutdown.
+    ///
+    /// Analyzers that hold buffered writes (e.g. [`file_logger::FileLogger`])
+    /// override this to fsync/finalize on a graceful shutdown. The default
+    /// is a no-op so most analyzers don't need to care.
+    async fn flush(&mut self) -> Result<(), AnalyzerError> {
+        Ok(())
+    }
 }
 
 pub mod output;
@@ -25,18 +34,28 @@ pub mod ssl_filter;
 pub mod event;
 pub mod common;
 pub mod timestamp_normalizer;
+pub mod compression;
+pub mod http_transaction_correlator;
+pub mod bench;
+pub mod metrics;
+pub mod forward;
 
 #[cfg(test)]
 mod sse_processor_tests;
 
 pub use output::OutputAnalyzer;
-pub use file_logger::FileLogger;
-pub use sse_processor::SSEProcessor;
+pub use file_logger::{FileLogger, Format};
+pub use sse_processor::{SSEProcessor, SseSchema, AnthropicSchema, OpenAiSchema, GeminiSchema};
 pub use http_parser::HTTPParser;
-pub use http_filter::{HTTPFilter, print_global_http_filter_metrics};
+pub use http_filter::{HTTPFilter, Rule, RuleAction, RuleSeverity, RuleDiagnostic, AlertedEvent};
 pub use auth_header_remover::AuthHeaderRemover;
 pub use ssl_filter::{SSLFilter, print_global_ssl_filter_metrics};
 pub use timestamp_normalizer::TimestampNormalizer;
+pub use compression::{CompressionAnalyzer, CompressionAlgorithm};
+pub use http_transaction_correlator::HTTPTransactionCorrelator;
+pub use bench::{run_replay, print_replay_report, StageReport, ReplaySummary};
+pub use metrics::{MetricsCollector, print_global_prometheus_metrics, render_global_prometheus_metrics, record_analyzer_process, record_filter_match, AnalyzerOutcome};
+pub use forward::{ForwardAnalyzer, ForwardConfig, ForwardFormat, print_global_forward_metrics};
 
 #[cfg(test)]
 mod comprehensive_analyzer_chain_tests {
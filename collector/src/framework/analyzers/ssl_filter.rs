@@ -58,14 +58,17 @@ impl SSLFilter {
         }
     }
 
-    /// Create a new SSL filter with exclude patterns
-    pub fn with_patterns(patterns: Vec<String>) -> Self {
+    /// Create a new SSL filter with exclude patterns.
+    ///
+    /// Returns the first malformed pattern's [`FilterParseError`] instead of
+    /// silently compiling it down to a filter that matches nothing.
+    pub fn with_patterns(patterns: Vec<String>) -> Result<Self, FilterParseError> {
         let mut filter = SSLFilter::new();
         filter.exclude_patterns = patterns.clone();
         filter.filters = patterns.into_iter()
             .map(|p| FilterExpression::parse(&p))
-            .collect();
-        filter
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(filter)
     }
 
 
@@ -75,47 +78,167 @@ impl SSLFilter {
 }
 
 
+/// Fields `FilterExpression::parse` knows how to evaluate a condition
+/// against. Anything else is reported as [`FilterParseErrorReason::UnknownField`]
+/// rather than silently compiling to a condition that never matches.
+const KNOWN_FIELDS: &[&str] = &[
+    "data", "function", "comm", "len", "pid", "tid", "uid",
+    "latency_ms", "timestamp_ns", "is_handshake", "truncated", "data.type",
+];
+
+/// Why [`FilterExpression::parse`] rejected an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterParseErrorReason {
+    /// The field name isn't one of [`KNOWN_FIELDS`].
+    UnknownField(String),
+    /// An `&`/`|` had nothing (or only whitespace) on one side.
+    EmptyOperand,
+    /// A `(` was never closed by a matching `)` (or vice versa).
+    UnterminatedParenthesis,
+    /// No comparison operator (`=`, `!=`, `>`, `<`, `>=`, `<=`, `~`) was found.
+    MissingOperator,
+}
+
+impl std::fmt::Display for FilterParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownField(field) => write!(
+                f,
+                "unknown field `{}` (expected one of {})",
+                field,
+                KNOWN_FIELDS.join("/")
+            ),
+            Self::EmptyOperand => write!(f, "empty operand around `&`/`|`"),
+            Self::UnterminatedParenthesis => write!(f, "unterminated parenthesis"),
+            Self::MissingOperator => write!(f, "missing operator (expected one of =, !=, >, <, >=, <=, ~)"),
+        }
+    }
+}
+
+/// A malformed filter expression, carrying enough context (the original
+/// text, a byte offset into it, and the reason) to point a user at exactly
+/// what's wrong instead of the expression silently compiling down to a
+/// filter that matches nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterParseError {
+    expression: String,
+    offset: usize,
+    reason: FilterParseErrorReason,
+}
+
+impl FilterParseError {
+    fn new(expression: &str, offset: usize, reason: FilterParseErrorReason) -> Self {
+        Self { expression: expression.to_string(), offset, reason }
+    }
+
+    /// Byte offset into the original expression where the problem starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Why the expression was rejected.
+    pub fn reason(&self) -> &FilterParseErrorReason {
+        &self.reason
+    }
+}
+
+impl std::fmt::Display for FilterParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid filter expression `{}` at byte {}: {}", self.expression, self.offset, self.reason)
+    }
+}
+
+impl std::error::Error for FilterParseError {}
+
 impl FilterExpression {
-    /// Parse a filter expression string
-    pub fn parse(expression: &str) -> Self {
-        let parsed = Self::parse_expression(expression);
-        FilterExpression {
+    /// Parse a filter expression string, reporting a [`FilterParseError`]
+    /// (with a byte offset into `expression`) instead of silently falling
+    /// back to a filter that matches nothing.
+    pub fn parse(expression: &str) -> Result<Self, FilterParseError> {
+        Self::check_parens_balanced(expression)?;
+        let parsed = Self::parse_expression(expression, expression)?;
+        Ok(FilterExpression {
             expression: expression.to_string(),
             parsed,
+        })
+    }
+
+    /// Byte offset of the subslice `sub` within `full`. `sub` must actually
+    /// be (a view into) `full`'s own buffer - true for every subslice this
+    /// parser produces, since it only ever slices, trims and re-slices the
+    /// original string rather than copying it - so this is a cheap way to
+    /// recover "where in the original expression did this go wrong" without
+    /// threading an offset parameter through every recursive call.
+    fn offset_within(full: &str, sub: &str) -> usize {
+        (sub.as_ptr() as usize).saturating_sub(full.as_ptr() as usize)
+    }
+
+    /// Verify every `(` has a matching `)` (and vice versa) before parsing
+    /// starts, so an unbalanced expression is reported with a precise
+    /// location rather than silently failing to find an operator later on.
+    fn check_parens_balanced(expr: &str) -> Result<(), FilterParseError> {
+        let mut open_positions = Vec::new();
+
+        for (i, c) in expr.char_indices() {
+            match c {
+                '(' => open_positions.push(i),
+                ')' => {
+                    if open_positions.pop().is_none() {
+                        return Err(FilterParseError::new(expr, i, FilterParseErrorReason::UnterminatedParenthesis));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(&unclosed) = open_positions.first() {
+            return Err(FilterParseError::new(expr, unclosed, FilterParseErrorReason::UnterminatedParenthesis));
         }
+
+        Ok(())
     }
 
     /// Parse an expression string into a FilterNode tree
-    fn parse_expression(expr: &str) -> FilterNode {
+    fn parse_expression(full: &str, expr: &str) -> Result<FilterNode, FilterParseError> {
         let expr = expr.trim();
-        
+
         if expr.is_empty() {
-            return FilterNode::Empty;
+            return Ok(FilterNode::Empty);
         }
 
         // Handle OR operations (lowest precedence)
         if let Some(or_pos) = Self::find_operator(expr, '|') {
-            let left = Self::parse_expression(&expr[..or_pos]);
-            let right = Self::parse_expression(&expr[or_pos + 1..]);
-            return FilterNode::Or(Box::new(left), Box::new(right));
+            let (left_src, right_src) = (&expr[..or_pos], &expr[or_pos + 1..]);
+            if left_src.trim().is_empty() || right_src.trim().is_empty() {
+                let empty_at = if left_src.trim().is_empty() { expr } else { right_src };
+                return Err(FilterParseError::new(full, Self::offset_within(full, empty_at), FilterParseErrorReason::EmptyOperand));
+            }
+            let left = Self::parse_expression(full, left_src)?;
+            let right = Self::parse_expression(full, right_src)?;
+            return Ok(FilterNode::Or(Box::new(left), Box::new(right)));
         }
 
         // Handle AND operations (higher precedence)
         if let Some(and_pos) = Self::find_operator(expr, '&') {
-            let left = Self::parse_expression(&expr[..and_pos]);
-            let right = Self::parse_expression(&expr[and_pos + 1..]);
-            return FilterNode::And(Box::new(left), Box::new(right));
+            let (left_src, right_src) = (&expr[..and_pos], &expr[and_pos + 1..]);
+            if left_src.trim().is_empty() || right_src.trim().is_empty() {
+                let empty_at = if left_src.trim().is_empty() { expr } else { right_src };
+                return Err(FilterParseError::new(full, Self::offset_within(full, empty_at), FilterParseErrorReason::EmptyOperand));
+            }
+            let left = Self::parse_expression(full, left_src)?;
+            let right = Self::parse_expression(full, right_src)?;
+            return Ok(FilterNode::And(Box::new(left), Box::new(right)));
         }
 
         // Parse single condition
-        Self::parse_condition(expr)
+        Self::parse_condition(full, expr)
     }
 
     /// Find the position of an operator at the top level (not inside parentheses)
     fn find_operator(expr: &str, op: char) -> Option<usize> {
         let mut paren_depth = 0;
         let chars: Vec<char> = expr.chars().collect();
-        
+
         for (i, &c) in chars.iter().enumerate() {
             match c {
                 '(' => paren_depth += 1,
@@ -128,27 +251,31 @@ impl FilterExpression {
     }
 
     /// Parse a single condition like "data=0\r\n\r\n" or "function=READ/RECV"
-    fn parse_condition(expr: &str) -> FilterNode {
+    fn parse_condition(full: &str, expr: &str) -> Result<FilterNode, FilterParseError> {
         let expr = expr.trim();
-        
+
         // Handle parentheses
         if expr.starts_with('(') && expr.ends_with(')') {
-            return Self::parse_expression(&expr[1..expr.len()-1]);
+            return Self::parse_expression(full, &expr[1..expr.len()-1]);
         }
 
         // Find the operator
         let operators = [">=", "<=", "!=", "=", ">", "<", "~"];
-        
+
         for &op in &operators {
             if let Some(pos) = expr.find(op) {
                 let field = expr[..pos].trim().to_string();
                 let value = expr[pos + op.len()..].trim().to_string();
-                
+
+                if !KNOWN_FIELDS.contains(&field.as_str()) {
+                    return Err(FilterParseError::new(full, Self::offset_within(full, expr), FilterParseErrorReason::UnknownField(field)));
+                }
+
                 let operator = match op {
                     "=" => "exact",
                     "!=" => "not_equal",
                     ">" => "gt",
-                    "<" => "lt", 
+                    "<" => "lt",
                     ">=" => "gte",
                     "<=" => "lte",
                     "~" => "contains",
@@ -157,11 +284,11 @@ impl FilterExpression {
 
                 // Process escape sequences in the value
                 let processed_value = Self::process_escape_sequences(&value);
-                return FilterNode::Condition { field, operator, value: processed_value };
+                return Ok(FilterNode::Condition { field, operator, value: processed_value });
             }
         }
 
-        FilterNode::Empty
+        Err(FilterParseError::new(full, Self::offset_within(full, expr), FilterParseErrorReason::MissingOperator))
     }
 
     /// Process escape sequences in filter values
@@ -404,7 +531,7 @@ mod tests {
 
     #[test]
     fn test_ssl_filter_expression_parsing() {
-        let expr = FilterExpression::parse("function=READ/RECV");
+        let expr = FilterExpression::parse("function=READ/RECV").unwrap();
         match expr.parsed {
             FilterNode::Condition { field, operator, value } => {
                 assert_eq!(field, "function");
@@ -418,7 +545,7 @@ mod tests {
     #[test]
     fn test_ssl_data_filtering() {
         // Use 'contains' operator for pattern matching
-        let filter = FilterExpression::parse("data~chunked");
+        let filter = FilterExpression::parse("data~chunked").unwrap();
         
         let matching_event = json!({
             "data": "chunked data here",
@@ -438,7 +565,7 @@ mod tests {
 
     #[test]
     fn test_ssl_function_filtering() {
-        let filter = FilterExpression::parse("function=READ/RECV");
+        let filter = FilterExpression::parse("function=READ/RECV").unwrap();
         
         let read_event = json!({
             "data": "some data",
@@ -458,7 +585,7 @@ mod tests {
 
     #[test]
     fn test_ssl_numeric_filtering() {
-        let filter = FilterExpression::parse("len<10");
+        let filter = FilterExpression::parse("len<10").unwrap();
         
         let small_event = json!({
             "data": "small",
@@ -478,7 +605,7 @@ mod tests {
 
     #[test]
     fn test_ssl_complex_expressions() {
-        let filter = FilterExpression::parse("data~chunked&function=READ/RECV");
+        let filter = FilterExpression::parse("data~chunked&function=READ/RECV").unwrap();
         
         let matching_event = json!({
             "data": "chunked data here",
@@ -517,7 +644,7 @@ mod tests {
         assert_eq!(processed3, "quote\"test\\");
         
         // Test with actual SSL data pattern
-        let filter = FilterExpression::parse("data=0\\r\\n\\r\\n");
+        let filter = FilterExpression::parse("data=0\\r\\n\\r\\n").unwrap();
         
         let matching_event = json!({
             "data": "0\r\n\r\n",
@@ -534,6 +661,45 @@ mod tests {
         assert!(filter.evaluate(&matching_event));
         assert!(!filter.evaluate(&non_matching_event));
     }
+
+    #[test]
+    fn test_parse_rejects_unknown_field() {
+        let err = FilterExpression::parse("latenci_ms>5").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::UnknownField("latenci_ms".to_string()));
+        assert_eq!(err.offset(), 0);
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_operand() {
+        let err = FilterExpression::parse("data~chunked&").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::EmptyOperand);
+    }
+
+    #[test]
+    fn test_parse_rejects_unterminated_parenthesis() {
+        let err = FilterExpression::parse("(data=1&function=READ").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::UnterminatedParenthesis);
+        assert_eq!(err.offset(), 0);
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_operator() {
+        let err = FilterExpression::parse("data~chunked&just_a_field").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::MissingOperator);
+    }
+
+    #[test]
+    fn test_parse_error_offset_points_at_the_malformed_condition() {
+        let err = FilterExpression::parse("data=ok&bogus_field=1").unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::UnknownField("bogus_field".to_string()));
+        assert_eq!(err.offset(), "data=ok&".len());
+    }
+
+    #[test]
+    fn test_with_patterns_surfaces_parse_error() {
+        let err = SSLFilter::with_patterns(vec!["data=ok".to_string(), "just_a_field".to_string()]).unwrap_err();
+        assert_eq!(err.reason(), &FilterParseErrorReason::MissingOperator);
+    }
 }
 
 // Global metrics storage for SSL filter
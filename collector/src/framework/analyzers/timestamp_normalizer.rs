@@ -5,7 +5,7 @@
 
 use super::Analyzer;
 use crate::framework::core::Event;
-use crate::framework::core::timestamp::boot_ns_to_epoch_ms;
+use crate::framework::core::timestamp::{boot_ns_to_epoch_ms, ClockSource};
 use async_trait::async_trait;
 use futures::stream::{Stream, StreamExt};
 use std::pin::Pin;
@@ -14,13 +14,25 @@ type EventStream = Pin<Box<dyn Stream<Item = Event> + Send>>;
 
 #[derive(Debug)]
 pub struct TimestampNormalizer {
+    clock_source: ClockSource,
 }
 
 impl TimestampNormalizer {
+    /// Defaults to [`ClockSource::Monotonic`] (`bpf_ktime_get_ns()`), the
+    /// clock every tracer in this crate's eBPF programs has historically
+    /// used. Use [`Self::with_clock_source`] for a tracer that instead reads
+    /// `bpf_ktime_get_boot_ns()`.
     pub fn new() -> Self {
         Self {
+            clock_source: ClockSource::Monotonic,
         }
     }
+
+    /// Override which clock `event.timestamp` was read from.
+    pub fn with_clock_source(mut self, clock_source: ClockSource) -> Self {
+        self.clock_source = clock_source;
+        self
+    }
 }
 
 impl Default for TimestampNormalizer {
@@ -32,9 +44,10 @@ impl Default for TimestampNormalizer {
 #[async_trait]
 impl Analyzer for TimestampNormalizer {
     async fn process(&mut self, stream: EventStream) -> Result<EventStream, Box<dyn std::error::Error + Send + Sync>> {
-        let normalized_stream = stream.map(|mut event| {
+        let clock_source = self.clock_source;
+        let normalized_stream = stream.map(move |mut event| {
             // Convert timestamp from nanoseconds since boot to milliseconds since UNIX epoch
-            let timestamp_ms = boot_ns_to_epoch_ms(event.timestamp);
+            let timestamp_ms = boot_ns_to_epoch_ms(event.timestamp, clock_source);
             event.timestamp = timestamp_ms;
             event
         });
@@ -120,4 +133,24 @@ mod tests {
             assert!(result.timestamp > 1_000_000_000_000); // > year 2001
         }
     }
+
+    #[tokio::test]
+    async fn test_with_clock_source_overrides_the_default() {
+        let mut normalizer = TimestampNormalizer::new().with_clock_source(ClockSource::BootTime);
+        assert_eq!(normalizer.clock_source, ClockSource::BootTime);
+
+        let test_event = Event::new_with_timestamp(
+            1_000_000_000,
+            "test".to_string(),
+            1234,
+            "test_comm".to_string(),
+            json!({"test": "data"}),
+        );
+
+        let input_stream = stream::iter(vec![test_event]);
+        let output_stream = normalizer.process(Box::pin(input_stream)).await.unwrap();
+
+        let results: Vec<Event> = output_stream.collect().await;
+        assert!(results[0].timestamp > 1_000_000_000_000);
+    }
 }
@@ -4,66 +4,159 @@
 /// for consistency and ease of use in the frontend.
 
 use std::fs;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Cached boot time in seconds since UNIX epoch
-static BOOT_TIME_SECS: OnceLock<i64> = OnceLock::new();
+/// Which eBPF clock a raw timestamp passed to [`boot_ns_to_epoch_ms`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+    /// `bpf_ktime_get_ns()` - CLOCK_MONOTONIC, which freezes while the
+    /// machine is suspended, so it needs anchoring to wall-clock time rather
+    /// than a simple boot-time offset.
+    Monotonic,
+    /// `bpf_ktime_get_boot_ns()` - CLOCK_BOOTTIME, which keeps advancing
+    /// through suspend, so `boot_time_secs + offset` stays accurate.
+    BootTime,
+}
 
-/// Get the system boot time in seconds since UNIX epoch
-///
-/// This reads from /proc/stat (btime field) and caches the result.
-/// Falls back to calculating from /proc/uptime if btime is not available.
-pub fn get_boot_time_secs() -> i64 {
-    *BOOT_TIME_SECS.get_or_init(|| {
-        // Try to read from /proc/stat (most reliable)
-        if let Ok(content) = fs::read_to_string("/proc/stat") {
-            for line in content.lines() {
-                if line.starts_with("btime ") {
-                    if let Some(btime_str) = line.split_whitespace().nth(1) {
-                        if let Ok(btime) = btime_str.parse::<i64>() {
-                            return btime;
-                        }
+/// How many [`boot_ns_to_epoch_ms`] calls to make between re-reading
+/// `/proc/stat`'s `btime` and re-anchoring `CLOCK_MONOTONIC`, so a suspend/
+/// resume cycle or an NTP step doesn't leave conversions drifting from real
+/// UNIX time for the lifetime of the process.
+const ANCHOR_REFRESH_CONVERSIONS: u64 = 10_000;
+
+/// Snapshot of everything [`boot_ns_to_epoch_ms`] needs to convert either
+/// clock source, taken together so both halves stay consistent with each
+/// other across a refresh.
+struct ClockAnchor {
+    /// `/proc/stat`'s `btime`, for [`ClockSource::BootTime`] conversions.
+    boot_time_secs: i64,
+    /// Wall-clock time at the moment this anchor was taken, for
+    /// [`ClockSource::Monotonic`] conversions.
+    epoch_ms_anchor: i64,
+    /// `CLOCK_MONOTONIC` reading at the moment this anchor was taken.
+    monotonic_ns_anchor: u64,
+}
+
+static CLOCK_ANCHOR: OnceLock<Mutex<ClockAnchor>> = OnceLock::new();
+static CONVERSIONS_SINCE_REFRESH: AtomicU64 = AtomicU64::new(0);
+
+/// Read `/proc/stat`'s `btime` (falling back to `/proc/uptime`, then to the
+/// current time) without any caching - the raw read behind both
+/// [`get_boot_time_secs`] and the periodic anchor refresh.
+fn read_boot_time_secs() -> i64 {
+    // Try to read from /proc/stat (most reliable)
+    if let Ok(content) = fs::read_to_string("/proc/stat") {
+        for line in content.lines() {
+            if line.starts_with("btime ") {
+                if let Some(btime_str) = line.split_whitespace().nth(1) {
+                    if let Ok(btime) = btime_str.parse::<i64>() {
+                        return btime;
                     }
                 }
             }
         }
+    }
 
-        // Fallback: calculate from uptime
-        if let Ok(uptime_str) = fs::read_to_string("/proc/uptime") {
-            if let Some(uptime_secs_str) = uptime_str.split_whitespace().next() {
-                if let Ok(uptime_secs) = uptime_secs_str.parse::<f64>() {
-                    let now_secs = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs() as i64;
-                    return now_secs - uptime_secs as i64;
-                }
+    // Fallback: calculate from uptime
+    if let Ok(uptime_str) = fs::read_to_string("/proc/uptime") {
+        if let Some(uptime_secs_str) = uptime_str.split_whitespace().next() {
+            if let Ok(uptime_secs) = uptime_secs_str.parse::<f64>() {
+                let now_secs = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs() as i64;
+                return now_secs - uptime_secs as i64;
             }
         }
+    }
 
-        // Last resort: return current time (will be incorrect but won't crash)
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
-    })
+    // Last resort: return current time (will be incorrect but won't crash)
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Read the current `CLOCK_MONOTONIC` value in nanoseconds, the same clock
+/// `bpf_ktime_get_ns()` reads in-kernel.
+fn read_monotonic_ns() -> u64 {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+    }
+    ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64
+}
+
+fn fresh_anchor() -> ClockAnchor {
+    ClockAnchor {
+        boot_time_secs: read_boot_time_secs(),
+        epoch_ms_anchor: now_epoch_ms() as i64,
+        monotonic_ns_anchor: read_monotonic_ns(),
+    }
+}
+
+fn clock_anchor() -> &'static Mutex<ClockAnchor> {
+    CLOCK_ANCHOR.get_or_init(|| Mutex::new(fresh_anchor()))
+}
+
+/// Re-read `btime` and re-anchor `CLOCK_MONOTONIC` every
+/// `ANCHOR_REFRESH_CONVERSIONS` conversions, instead of caching either
+/// forever, so a suspend/resume cycle or a wall-clock step eventually gets
+/// corrected rather than permanently skewing converted timestamps.
+fn refresh_anchor_if_due() {
+    let count = CONVERSIONS_SINCE_REFRESH.fetch_add(1, Ordering::Relaxed);
+    if count % ANCHOR_REFRESH_CONVERSIONS == 0 {
+        *clock_anchor().lock().unwrap() = fresh_anchor();
+    }
+}
+
+/// Get the system boot time in seconds since UNIX epoch
+///
+/// This reads from /proc/stat (btime field) and caches the result,
+/// periodically refreshing it (see [`ANCHOR_REFRESH_CONVERSIONS`]) so a
+/// later NTP step is eventually reflected rather than cached forever.
+pub fn get_boot_time_secs() -> i64 {
+    clock_anchor().lock().unwrap().boot_time_secs
 }
 
-/// Convert nanoseconds since boot to milliseconds since UNIX epoch
+/// Convert a raw eBPF timestamp to milliseconds since UNIX epoch.
 ///
-/// This is used to convert eBPF timestamps (from bpf_ktime_get_ns()) to standard UNIX timestamps.
+/// `source` says which clock `ns_since_boot` came from:
+/// - [`ClockSource::BootTime`] (`bpf_ktime_get_boot_ns()`) is converted as
+///   `boot_time_secs * 1000 + ns_since_boot / 1e6`, since it already
+///   accounts for any time spent suspended.
+/// - [`ClockSource::Monotonic`] (`bpf_ktime_get_ns()`) freezes during
+///   suspend, so it's converted relative to a `CLOCK_MONOTONIC` anchor taken
+///   at (or near) process startup: `anchor_epoch_ms + (ns_since_boot -
+///   anchor_monotonic_ns) / 1e6`.
+///
+/// Both anchors are periodically re-read (see [`ANCHOR_REFRESH_CONVERSIONS`])
+/// to correct for suspend/resume and wall-clock steps instead of drifting
+/// for the lifetime of a long-running capture.
 ///
 /// # Arguments
-/// * `ns_since_boot` - Nanoseconds since system boot (from bpf_ktime_get_ns())
+/// * `ns_since_boot` - Nanoseconds since system boot, from the clock named by `source`
+/// * `source` - Which clock produced `ns_since_boot`
 ///
 /// # Returns
 /// Milliseconds since UNIX epoch (1970-01-01 00:00:00 UTC)
-pub fn boot_ns_to_epoch_ms(ns_since_boot: u64) -> u64 {
-    let boot_time_secs = get_boot_time_secs();
-    let boot_time_ms = boot_time_secs * 1000;
-    let offset_ms = (ns_since_boot / 1_000_000) as i64;
-    (boot_time_ms + offset_ms) as u64
+pub fn boot_ns_to_epoch_ms(ns_since_boot: u64, source: ClockSource) -> u64 {
+    refresh_anchor_if_due();
+    let anchor = clock_anchor().lock().unwrap();
+
+    match source {
+        ClockSource::BootTime => {
+            let boot_time_ms = anchor.boot_time_secs * 1000;
+            let offset_ms = (ns_since_boot / 1_000_000) as i64;
+            (boot_time_ms + offset_ms) as u64
+        }
+        ClockSource::Monotonic => {
+            let delta_ns = ns_since_boot as i64 - anchor.monotonic_ns_anchor as i64;
+            (anchor.epoch_ms_anchor + delta_ns / 1_000_000) as u64
+        }
+    }
 }
 
 /// Get current time as milliseconds since UNIX epoch
@@ -95,7 +188,7 @@ mod tests {
     fn test_boot_ns_to_epoch_ms_conversion() {
         // Test with a known timestamp: 1000 seconds after boot
         let ns_since_boot = 1000_000_000_000u64; // 1000 seconds in nanoseconds
-        let result_ms = boot_ns_to_epoch_ms(ns_since_boot);
+        let result_ms = boot_ns_to_epoch_ms(ns_since_boot, ClockSource::BootTime);
 
         let boot_time = get_boot_time_secs();
         let expected_ms = (boot_time + 1000) * 1000;
@@ -103,6 +196,30 @@ mod tests {
         assert_eq!(result_ms, expected_ms as u64);
     }
 
+    #[test]
+    fn test_monotonic_conversion_matches_bootime_near_startup() {
+        // The monotonic anchor is taken near process/test startup, so
+        // converting a `ns_since_boot` close to "now" via `Monotonic` should
+        // land close to converting it via `BootTime`.
+        let ns_since_boot = read_monotonic_ns();
+        let boottime_ms = boot_ns_to_epoch_ms(ns_since_boot, ClockSource::BootTime);
+        let monotonic_ms = boot_ns_to_epoch_ms(ns_since_boot, ClockSource::Monotonic);
+
+        let diff = (boottime_ms as i64 - monotonic_ms as i64).abs();
+        assert!(diff < 1000, "expected conversions to agree within 1s, diff={diff}ms");
+    }
+
+    #[test]
+    fn test_monotonic_conversion_tracks_elapsed_time() {
+        let anchor_ns = read_monotonic_ns();
+        let anchor_ms = boot_ns_to_epoch_ms(anchor_ns, ClockSource::Monotonic);
+
+        let later_ns = anchor_ns + 5_000_000_000; // 5 seconds later
+        let later_ms = boot_ns_to_epoch_ms(later_ns, ClockSource::Monotonic);
+
+        assert_eq!(later_ms - anchor_ms, 5000);
+    }
+
     #[test]
     fn test_now_epoch_ms() {
         let now_ms = now_epoch_ms();
@@ -1,13 +1,57 @@
-use super::{Runner, EventStream, RunnerError};
+use super::common::RunnerProgressTracker;
+use super::{Runner, EventStream, RunnerError, RunnerProgress, RunnerState};
 use crate::framework::analyzers::Analyzer;
 use async_trait::async_trait;
-use futures::stream::select_all;
+use futures::stream::{select_all, StreamExt};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Backoff and retry policy used by [`AgentRunner::with_supervision`].
+///
+/// Failed or exhausted runners are retried with exponential backoff and
+/// jitter, starting at `base_delay` and capped at `max_delay`. The delay
+/// resets to `base_delay` once a runner successfully emits at least one
+/// event, so a flaky-but-recovering runner doesn't get stuck at the cap.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    /// Initial delay before the first retry
+    pub base_delay: Duration,
+    /// Multiplier applied to the delay after each consecutive failure
+    pub factor: f64,
+    /// Upper bound on the backoff delay
+    pub max_delay: Duration,
+    /// Maximum number of restarts per runner, `None` means unlimited
+    pub max_restarts: Option<u32>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(200),
+            factor: 2.0,
+            max_delay: Duration::from_secs(30),
+            max_restarts: None,
+        }
+    }
+}
+
+/// Per-runner restart counters exposed via [`AgentRunner::restart_stats`].
+#[derive(Debug, Clone)]
+pub struct RestartStats {
+    pub runner_id: String,
+    pub restarts: u32,
+}
 
 /// AgentRunner composes multiple runners into a single unified stream
 /// with optional global analyzers applied to the merged stream
 pub struct AgentRunner {
     runners: Vec<Box<dyn Runner>>,
     analyzers: Vec<Box<dyn Analyzer>>,
+    supervision: Option<RestartPolicy>,
+    restart_counters: Vec<(String, Arc<AtomicU32>)>,
+    progress: Arc<RunnerProgressTracker>,
 }
 
 impl AgentRunner {
@@ -16,64 +60,217 @@ impl AgentRunner {
         Self {
             runners: Vec::new(),
             analyzers: Vec::new(),
+            supervision: None,
+            restart_counters: Vec::new(),
+            progress: Arc::new(RunnerProgressTracker::new("agent")),
         }
     }
-    
+
     /// Add a pre-configured runner with its analyzer chain
     pub fn add_runner(mut self, runner: Box<dyn Runner>) -> Self {
         self.runners.push(runner);
         self
     }
-    
+
     /// Add analyzer that will be applied to the merged stream
     pub fn add_global_analyzer(mut self, analyzer: Box<dyn Analyzer>) -> Self {
         self.analyzers.push(analyzer);
         self
     }
-    
+
+    /// Enable self-healing supervision: instead of running each runner once
+    /// and merging fixed streams with `select_all`, every runner is driven
+    /// by its own task that restarts it with backoff when `run()` errors or
+    /// its stream ends. The merged stream stays open as long as at least
+    /// one supervised task is alive, and dropping it shuts every task down.
+    pub fn with_supervision(mut self, policy: RestartPolicy) -> Self {
+        self.supervision = Some(policy);
+        self
+    }
+
     /// Get the number of configured runners
     pub fn runner_count(&self) -> usize {
         self.runners.len()
     }
-    
+
     /// Get the number of configured global analyzers
     pub fn analyzer_count(&self) -> usize {
         self.analyzers.len()
     }
+
+    /// Restart counts recorded by the supervisor, keyed by runner id.
+    /// Empty when supervision is not enabled.
+    pub fn restart_stats(&self) -> Vec<RestartStats> {
+        self.restart_counters
+            .iter()
+            .map(|(id, counter)| RestartStats {
+                runner_id: id.clone(),
+                restarts: counter.load(Ordering::SeqCst),
+            })
+            .collect()
+    }
+
+    /// Drive every configured runner from its own supervised task, forwarding
+    /// events into a shared channel. A runner that errors out or whose stream
+    /// ends is restarted after a backoff delay instead of tearing down the
+    /// whole agent.
+    fn run_supervised(&mut self, policy: RestartPolicy) -> EventStream {
+        let (tx, rx) = tokio::sync::mpsc::channel(256);
+        self.restart_counters.clear();
+
+        for mut runner in std::mem::take(&mut self.runners) {
+            let tx = tx.clone();
+            let policy = policy.clone();
+            let runner_id = runner.id();
+            let counter = Arc::new(AtomicU32::new(0));
+            self.restart_counters.push((runner_id.clone(), counter.clone()));
+
+            tokio::spawn(async move {
+                let mut delay = policy.base_delay;
+                let mut restarts: u32 = 0;
+
+                loop {
+                    match runner.run().await {
+                        Ok(mut stream) => {
+                            let mut emitted_any = false;
+                            loop {
+                                match stream.next().await {
+                                    Some(event) => {
+                                        emitted_any = true;
+                                        delay = policy.base_delay;
+                                        if tx.send(event).await.is_err() {
+                                            // Receiver dropped: shut this task down.
+                                            return;
+                                        }
+                                    }
+                                    None => break,
+                                }
+                            }
+                            log::warn!(
+                                "Supervised runner '{}' stream ended, restarting",
+                                runner_id
+                            );
+                            let _ = emitted_any;
+                        }
+                        Err(e) => {
+                            log::warn!("Supervised runner '{}' failed: {}", runner_id, e);
+                        }
+                    }
+
+                    restarts += 1;
+                    counter.store(restarts, Ordering::SeqCst);
+                    if let Some(max) = policy.max_restarts {
+                        if restarts >= max {
+                            log::error!(
+                                "Supervised runner '{}' exhausted {} restarts, giving up",
+                                runner_id, max
+                            );
+                            return;
+                        }
+                    }
+
+                    tokio::time::sleep(jittered(delay)).await;
+                    delay = Duration::from_secs_f64(
+                        (delay.as_secs_f64() * policy.factor).min(policy.max_delay.as_secs_f64()),
+                    );
+                }
+            });
+        }
+
+        // Drop our own sender so the channel closes once every spawned task exits.
+        drop(tx);
+
+        Box::pin(ReceiverStream::new(rx))
+    }
+}
+
+/// Apply +/-20% jitter to a backoff delay using the low bits of the current
+/// time as an inexpensive source of randomness. Shared by other supervised
+/// sinks/runners (e.g. `RemoteSink`) that want the same backoff shape.
+pub(crate) fn jittered(delay: Duration) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let spread = 0.8 + ((nanos % 400) as f64 / 1000.0); // 0.8x - 1.2x
+    Duration::from_secs_f64(delay.as_secs_f64() * spread)
 }
 
 #[async_trait]
 impl Runner for AgentRunner {
     async fn run(&mut self) -> Result<EventStream, RunnerError> {
         if self.runners.is_empty() {
+            self.progress.mark_degraded("no runners configured");
             return Err("No runners configured for AgentRunner".into());
         }
-        
-        // Start all runners concurrently and collect their streams
-        let mut streams = Vec::new();
-        for runner in &mut self.runners {
-            let stream = runner.run().await?;
-            streams.push(stream);
-        }
-        
-        // Merge all streams into a single stream
-        let merged_stream = select_all(streams);
-        
+
+        let merged_stream = match self.supervision.clone() {
+            Some(policy) => self.run_supervised(policy),
+            None => {
+                // Start all runners concurrently and collect their streams
+                let mut streams = Vec::new();
+                for runner in &mut self.runners {
+                    let stream = runner.run().await?;
+                    streams.push(stream);
+                }
+
+                // Merge all streams into a single stream
+                Box::pin(select_all(streams)) as EventStream
+            }
+        };
+
         // Apply global analyzers to the merged stream
-        let mut final_stream = Box::pin(merged_stream) as EventStream;
+        let mut final_stream = merged_stream;
         for analyzer in &mut self.analyzers {
             final_stream = analyzer.process(final_stream).await
                 .map_err(|e| format!("Global analyzer error: {}", e))?;
         }
-        
-        Ok(final_stream)
+
+        self.progress.mark_running();
+        let progress = Arc::clone(&self.progress);
+        let tracked_stream = final_stream.inspect(move |event| progress.record_event(event));
+
+        Ok(Box::pin(tracked_stream))
     }
-    
+
+    /// Flush every sub-runner and global analyzer on a best-effort basis.
+    ///
+    /// In supervised mode `self.runners` has already been drained into
+    /// independently-spawned tasks by [`run_supervised`], so there's nothing
+    /// left here to flush - only the global analyzer chain is reachable.
+    /// In non-supervised mode `self.runners` is still owned, so each
+    /// sub-runner is flushed too.
+    async fn flush(&mut self) -> Result<(), RunnerError> {
+        let mut first_err: Option<RunnerError> = None;
+        for runner in &mut self.runners {
+            if let Err(e) = runner.flush().await {
+                log::warn!("Runner '{}' failed to flush: {}", runner.id(), e);
+                if first_err.is_none() {
+                    first_err = Some(e);
+                }
+            }
+        }
+        if let Err(e) = super::common::AnalyzerProcessor::flush_analyzers(&mut self.analyzers).await {
+            if first_err.is_none() {
+                first_err = Some(e);
+            }
+        }
+        self.progress.mark_stopped();
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn progress(&self) -> tokio::sync::watch::Receiver<RunnerProgress> {
+        self.progress.receiver()
+    }
+
     fn add_analyzer(mut self, analyzer: Box<dyn Analyzer>) -> Self {
         self.analyzers.push(analyzer);
         self
     }
-    
+
     fn name(&self) -> &str {
         "AgentRunner"
     }
@@ -377,4 +574,80 @@ mod tests {
         assert_eq!(agent.runner_count(), 2);
         assert_eq!(agent.analyzer_count(), 2); // Both global analyzers should be present
     }
+
+    #[tokio::test]
+    async fn test_agent_runner_supervision_restarts_exhausted_runner() {
+        // A runner whose stream always ends immediately should be restarted
+        // up to max_restarts, then the supervisor gives up on it quietly
+        // (the merged stream still closes once every task exits).
+        let fake_runner = FakeRunner::new().event_count(0).delay_ms(1);
+
+        let policy = RestartPolicy {
+            base_delay: Duration::from_millis(1),
+            factor: 2.0,
+            max_delay: Duration::from_millis(10),
+            max_restarts: Some(2),
+        };
+
+        let mut agent = AgentRunner::new("supervised-test")
+            .add_runner(Box::new(fake_runner))
+            .with_supervision(policy);
+
+        let stream = timeout(Duration::from_secs(5), agent.run())
+            .await
+            .expect("supervised run should not hang")
+            .unwrap();
+        let events: Vec<_> = timeout(Duration::from_secs(5), stream.collect())
+            .await
+            .expect("merged stream should close once restarts are exhausted");
+
+        assert!(events.is_empty());
+
+        let stats = agent.restart_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].restarts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_agent_runner_supervision_forwards_events() {
+        let fake_runner = FakeRunner::new().event_count(2).delay_ms(5);
+
+        let mut agent = AgentRunner::new("supervised-forward-test")
+            .add_runner(Box::new(fake_runner))
+            .with_supervision(RestartPolicy {
+                max_restarts: Some(0),
+                ..RestartPolicy::default()
+            });
+
+        let stream = agent.run().await.unwrap();
+        let events: Vec<_> = timeout(Duration::from_secs(5), stream.collect())
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 4); // 2 event_count * 2 events per count
+    }
+
+    #[tokio::test]
+    async fn test_agent_runner_progress_tracks_state_and_event_count() {
+        let mut agent = AgentRunner::new("progress-test")
+            .add_runner(Box::new(FakeRunner::new().event_count(2).delay_ms(5)));
+
+        let mut progress = agent.progress();
+        assert_eq!(progress.borrow().state, RunnerState::Starting);
+
+        let stream = agent.run().await.unwrap();
+        assert_eq!(progress.borrow_and_update().state, RunnerState::Running);
+
+        let events: Vec<_> = timeout(Duration::from_secs(5), stream.collect()).await.unwrap();
+        assert_eq!(events.len(), 4);
+
+        // The watch channel coalesces rapid updates, so only the latest
+        // snapshot (not one `changed()` per event) is guaranteed to be seen.
+        progress.changed().await.unwrap();
+        assert_eq!(progress.borrow_and_update().events_emitted, 4);
+
+        agent.flush().await.unwrap();
+        progress.changed().await.unwrap();
+        assert_eq!(progress.borrow().state, RunnerState::Stopped);
+    }
 }
\ No newline at end of file
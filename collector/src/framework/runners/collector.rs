@@ -0,0 +1,182 @@
+use super::EventStream;
+use crate::framework::core::Event;
+use futures::stream::StreamExt;
+use tokio::time::{Duration, Instant};
+
+/// Predicate used by [`EventCollector`] to decide which events to keep.
+///
+/// Boxed rather than generic over the closure type so a builder can be
+/// constructed and passed around without infecting callers with a type
+/// parameter, matching how [`super::remote_sink::Authenticator`] and other
+/// pluggable behaviors in this module are expressed as trait objects.
+pub type EventFilter = Box<dyn Fn(&Event) -> bool + Send + Sync>;
+
+/// Builder for subscribing to a slice of an `EventStream` matching a runtime
+/// predicate, without writing a dedicated [`Analyzer`](crate::framework::analyzers::Analyzer)
+/// for each one-off question ("collect the next 3 assistant responses on
+/// connection X longer than 1KB", "collect every event whose tool-call name
+/// is `shell`").
+///
+/// Unlike an `Analyzer`, this doesn't sit in the processing chain and
+/// transform every event for downstream consumers - it's a narrow,
+/// disposable subscription built on top of an already-merged stream (e.g.
+/// an `AgentRunner`'s output), so analysis code can `await` a specific
+/// agent behavior instead of consuming and re-matching the entire firehose.
+pub struct EventCollector {
+    filter: EventFilter,
+    timeout: Option<Duration>,
+    max: Option<usize>,
+}
+
+impl EventCollector {
+    /// Start a collector that keeps only events matching `filter`.
+    pub fn new(filter: impl Fn(&Event) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            filter: Box::new(filter),
+            timeout: None,
+            max: None,
+        }
+    }
+
+    /// Stop collecting once this much time has elapsed since the first
+    /// call to [`Self::collect`], regardless of how many matches arrived.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Stop collecting once this many matching events have been seen.
+    pub fn with_max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Consume `stream`, returning a new stream of just the events matching
+    /// this collector's filter. The returned stream ends when `max` matches
+    /// have been yielded, when `timeout` elapses, or when the upstream
+    /// stream itself ends - whichever happens first.
+    pub fn collect(self, mut stream: EventStream) -> EventStream {
+        let EventCollector { filter, timeout, max } = self;
+
+        Box::pin(async_stream::stream! {
+            let deadline = timeout.map(|d| Instant::now() + d);
+            let mut yielded = 0usize;
+
+            loop {
+                if let Some(max) = max {
+                    if yielded >= max {
+                        break;
+                    }
+                }
+
+                let next = match deadline {
+                    Some(deadline) => {
+                        let remaining = deadline.saturating_duration_since(Instant::now());
+                        if remaining.is_zero() {
+                            break;
+                        }
+                        match tokio::time::timeout(remaining, stream.next()).await {
+                            Ok(next) => next,
+                            Err(_) => break,
+                        }
+                    }
+                    None => stream.next().await,
+                };
+
+                match next {
+                    Some(event) => {
+                        if filter(&event) {
+                            yielded += 1;
+                            yield event;
+                        }
+                    }
+                    None => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(tool_name: &str) -> Event {
+        Event::new(
+            "ssl".to_string(),
+            1234,
+            "ssl".to_string(),
+            json!({"tool_call": {"name": tool_name}}),
+        )
+    }
+
+    fn input_stream(events: Vec<Event>) -> EventStream {
+        Box::pin(futures::stream::iter(events))
+    }
+
+    #[tokio::test]
+    async fn test_collect_filters_by_predicate() {
+        let events = vec![event("shell"), event("read_file"), event("shell")];
+
+        let collector = EventCollector::new(|e: &Event| e.data["tool_call"]["name"] == json!("shell"));
+        let mut out = collector.collect(input_stream(events));
+
+        let mut matched = Vec::new();
+        while let Some(event) = out.next().await {
+            matched.push(event);
+        }
+
+        assert_eq!(matched.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_collect_stops_at_max() {
+        let events = vec![event("shell"), event("shell"), event("shell")];
+
+        let collector = EventCollector::new(|_: &Event| true).with_max(2);
+        let mut out = collector.collect(input_stream(events));
+
+        let mut matched = Vec::new();
+        while let Some(event) = out.next().await {
+            matched.push(event);
+        }
+
+        assert_eq!(matched.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_collect_ignores_non_matching_events_towards_max() {
+        let events = vec![event("read_file"), event("shell"), event("read_file"), event("shell")];
+
+        let collector = EventCollector::new(|e: &Event| e.data["tool_call"]["name"] == json!("shell")).with_max(2);
+        let mut out = collector.collect(input_stream(events));
+
+        let mut matched = Vec::new();
+        while let Some(event) = out.next().await {
+            matched.push(event);
+        }
+
+        assert_eq!(matched.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_collect_times_out_even_if_max_never_reached() {
+        // `pending()` never resolves, so the collector can only stop via its
+        // deadline - this exercises the timeout path rather than the
+        // upstream-stream-ended path.
+        let never_ending: EventStream = Box::pin(futures::stream::iter(vec![event("shell")]).chain(futures::stream::pending()));
+
+        let collector = EventCollector::new(|_: &Event| true)
+            .with_max(10)
+            .with_timeout(Duration::from_millis(50));
+        let mut out = collector.collect(never_ending);
+
+        let mut matched = Vec::new();
+        while let Some(event) = out.next().await {
+            matched.push(event);
+        }
+
+        assert_eq!(matched.len(), 1);
+    }
+}
@@ -1,30 +1,296 @@
 use crate::framework::analyzers::Analyzer;
-use super::{EventStream, RunnerError};
+use crate::framework::core::Event;
+use super::{EventStream, RunnerError, RunnerProgress, RunnerState};
 use std::process::Stdio;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
 use log::debug;
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 use std::pin::Pin;
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, FramedRead};
+use std::time::Duration;
+use tokio::sync::watch;
+use arc_swap::ArcSwap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use sha3::{Digest, Sha3_256};
 
 
 
 /// Type alias for JSON stream
 pub type JsonStream = Pin<Box<dyn Stream<Item = serde_json::Value> + Send>>;
 
+/// Frames a child process's stdout into newline-delimited JSON values.
+///
+/// Unlike a naive "one JSON object per line" reader, this scans forward
+/// past newlines that fall inside a still-incomplete value (e.g. a
+/// pretty-printed, multi-line object) instead of giving up on the first
+/// `\n` it finds, so compact and indented JSON can be emitted
+/// interchangeably by the traced binary.
+pub struct JsonLineDecoder {
+    /// Offset into the buffer already scanned past without finding a
+    /// frame boundary, so re-running `decode` after more bytes arrive
+    /// doesn't re-scan from the start every time.
+    scan_from: usize,
+    /// Largest an unterminated frame is allowed to grow before it's
+    /// dropped instead of buffered indefinitely. `None` means unbounded.
+    max_frame_size: Option<usize>,
+    /// Set once the frame currently being accumulated has been found to
+    /// exceed `max_frame_size`, so its remaining bytes are discarded as
+    /// they arrive instead of re-triggering the oversize log on every call.
+    oversized: bool,
+}
+
+impl JsonLineDecoder {
+    pub fn new() -> Self {
+        Self { scan_from: 0, max_frame_size: None, oversized: false }
+    }
+
+    /// Create a decoder that drops (rather than buffers indefinitely) any
+    /// unterminated frame whose accumulated bytes exceed `max_frame_size`.
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self { scan_from: 0, max_frame_size: Some(max_frame_size), oversized: false }
+    }
+
+    /// Parse one candidate frame (the bytes up to, but not including, a
+    /// `\n`), logging and skipping it if it's not valid JSON.
+    fn parse_frame(candidate: &[u8]) -> Result<Option<serde_json::Value>, serde_json::Error> {
+        let text = String::from_utf8_lossy(candidate);
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str::<serde_json::Value>(trimmed).map(Some)
+    }
+}
+
+impl Default for JsonLineDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for JsonLineDecoder {
+    type Item = serde_json::Value;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            let newline_pos = match buf[self.scan_from..].iter().position(|b| *b == b'\n') {
+                Some(pos) => self.scan_from + pos,
+                None => {
+                    if let Some(max) = self.max_frame_size {
+                        if buf.len() > max {
+                            if !self.oversized {
+                                let err = ClassifiedRunnerError::new(
+                                    RunnerErrorKind::FrameTooLarge { limit: max },
+                                    "unterminated JSON frame exceeded the configured limit, dropping it",
+                                );
+                                log::warn!("{}", err);
+                                self.oversized = true;
+                            }
+                            // Discard what's buffered so far rather than
+                            // growing without bound while still waiting
+                            // for the newline that ends this frame.
+                            buf.clear();
+                            self.scan_from = 0;
+                        }
+                    }
+                    return Ok(None);
+                }
+            };
+
+            if self.oversized {
+                // This newline ends the oversized frame we've been
+                // discarding; skip past it and resume parsing normally.
+                buf.advance(newline_pos + 1);
+                self.scan_from = 0;
+                self.oversized = false;
+                continue;
+            }
+
+            match Self::parse_frame(&buf[..newline_pos]) {
+                Ok(Some(value)) => {
+                    buf.advance(newline_pos + 1);
+                    self.scan_from = 0;
+                    return Ok(Some(value));
+                }
+                Ok(None) => {
+                    // Blank line; drop it and keep scanning.
+                    buf.advance(newline_pos + 1);
+                    self.scan_from = 0;
+                }
+                Err(e) if e.is_eof() => {
+                    // The bytes buffered so far parse as an incomplete
+                    // value rather than an invalid one - most likely a
+                    // multi-line object whose closing brace is further
+                    // along. Keep the bytes buffered and look past this
+                    // newline for the one that actually closes it.
+                    if newline_pos + 1 >= buf.len() {
+                        self.scan_from = newline_pos + 1;
+                        return Ok(None);
+                    }
+                    self.scan_from = newline_pos + 1;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Failed to parse JSON frame: {} - Frame: {}",
+                        e,
+                        String::from_utf8_lossy(&buf[..newline_pos])
+                    );
+                    buf.advance(newline_pos + 1);
+                    self.scan_from = 0;
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        let remaining = buf.split();
+        self.scan_from = 0;
+        match Self::parse_frame(&remaining) {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                log::warn!(
+                    "Failed to parse trailing JSON frame at EOF: {} - Frame: {}",
+                    e,
+                    String::from_utf8_lossy(&remaining)
+                );
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Type alias for a JSON stream that surfaces mid-stream runner failures
+/// instead of silently dropping them, for callers that opt into
+/// `with_stream_error_parsing`.
+pub type FallibleJsonStream = Pin<Box<dyn Stream<Item = Result<serde_json::Value, RunnerError>> + Send>>;
+
+/// Stable classification of a `BinaryExecutor` failure, so callers can match
+/// on the cause instead of pattern-matching a free-form error message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunnerErrorKind {
+    /// The child process could not be spawned for a reason other than the
+    /// more specific variants below.
+    SpawnFailed,
+    /// The configured binary path doesn't exist.
+    BinaryNotFound,
+    /// The current user isn't allowed to execute the configured binary.
+    PermissionDenied,
+    /// The child exited on its own with a nonzero status.
+    NonZeroExit { code: Option<i32> },
+    /// Output from the child couldn't be decoded as UTF-8.
+    Utf8Decode,
+    /// The runner reported an in-band `__stream_error__` sentinel.
+    StreamError,
+    /// An unterminated JSON frame exceeded the configured max frame size
+    /// and was dropped instead of being buffered indefinitely.
+    FrameTooLarge { limit: usize },
+}
+
+impl std::fmt::Display for RunnerErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SpawnFailed => write!(f, "spawn failed"),
+            Self::BinaryNotFound => write!(f, "binary not found"),
+            Self::PermissionDenied => write!(f, "permission denied"),
+            Self::NonZeroExit { code: Some(code) } => write!(f, "nonzero exit (code {})", code),
+            Self::NonZeroExit { code: None } => write!(f, "nonzero exit (no code, terminated by signal)"),
+            Self::Utf8Decode => write!(f, "UTF-8 decode error"),
+            Self::StreamError => write!(f, "stream error"),
+            Self::FrameTooLarge { limit } => write!(f, "frame exceeded max_frame_size ({} bytes)", limit),
+        }
+    }
+}
+
+/// A `BinaryExecutor` failure carrying both a human-readable message and its
+/// [`RunnerErrorKind`] classification.
+#[derive(Debug)]
+pub struct ClassifiedRunnerError {
+    kind: RunnerErrorKind,
+    message: String,
+}
+
+impl ClassifiedRunnerError {
+    pub fn new(kind: RunnerErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
+
+    /// The structured cause of this error.
+    pub fn kind(&self) -> &RunnerErrorKind {
+        &self.kind
+    }
+
+    fn boxed(self) -> RunnerError {
+        Box::new(self)
+    }
+}
+
+impl std::fmt::Display for ClassifiedRunnerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for ClassifiedRunnerError {}
+
+/// Map the `std::io::Error` from a failed spawn onto a [`RunnerErrorKind`].
+fn classify_spawn_error(e: &std::io::Error) -> RunnerErrorKind {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => RunnerErrorKind::BinaryNotFound,
+        std::io::ErrorKind::PermissionDenied => RunnerErrorKind::PermissionDenied,
+        _ => RunnerErrorKind::SpawnFailed,
+    }
+}
+
+/// How long to wait for the child to exit on its own after SIGTERM before
+/// escalating to SIGKILL.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default size of each read from the child's stdout, so high-volume
+/// tracers are consumed in bounded pieces instead of one unbounded read.
+const DEFAULT_READ_CHUNK_SIZE: usize = 8 * 1024;
+
 /// Common binary executor for runners - now supports streaming
 pub struct BinaryExecutor {
     binary_path: String,
     additional_args: Vec<String>,
     runner_name: Option<String>,
+    envs: std::collections::HashMap<String, String>,
+    /// Whether `{"__stream_error__": "...", "code": N}` sentinel objects on
+    /// stdout should be pulled out of the JSON stream and surfaced as a
+    /// `RunnerError` instead of being yielded like any other event.
+    parse_stream_error: bool,
+    /// Grace period given to the child after SIGTERM before it's
+    /// force-killed with SIGKILL (see [`force_kill`]).
+    ///
+    /// [`force_kill`]: BinaryExecutor::force_kill
+    shutdown_timeout: Duration,
+    /// Largest an unterminated JSON frame may grow before it's dropped (see
+    /// [`with_max_frame_size`]). `None` means unbounded.
+    ///
+    /// [`with_max_frame_size`]: BinaryExecutor::with_max_frame_size
+    max_frame_size: Option<usize>,
+    /// Size of each read from the child's stdout.
+    read_chunk_size: usize,
 }
 
 impl BinaryExecutor {
     pub fn new(binary_path: String) -> Self {
-        Self { 
+        Self {
             binary_path,
             additional_args: Vec::new(),
             runner_name: None,
+            envs: std::collections::HashMap::new(),
+            parse_stream_error: false,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            max_frame_size: None,
+            read_chunk_size: DEFAULT_READ_CHUNK_SIZE,
         }
     }
 
@@ -40,58 +306,123 @@ impl BinaryExecutor {
         self
     }
 
-    /// Execute binary and get raw JSON stream
-    pub async fn get_json_stream(&self) -> Result<JsonStream, RunnerError> {
+    /// Set extra environment variables for the spawned binary, on top of
+    /// the inherited process environment
+    pub fn with_envs(mut self, envs: std::collections::HashMap<String, String>) -> Self {
+        self.envs = envs;
+        self
+    }
+
+    /// Set how long to wait for the child to exit on its own after SIGTERM
+    /// before escalating to SIGKILL (see [`force_kill`]).
+    ///
+    /// [`force_kill`]: BinaryExecutor::force_kill
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Drop (rather than buffer indefinitely) any unterminated JSON frame
+    /// whose accumulated bytes exceed `max_frame_size`, so a runner that
+    /// emits a gigantic single line or never emits a newline can't grow the
+    /// read buffer without bound.
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = Some(max_frame_size);
+        self
+    }
+
+    /// Set the size of each read from the child's stdout, so large outputs
+    /// are consumed in bounded pieces rather than one unbounded read.
+    pub fn with_read_chunk_size(mut self, read_chunk_size: usize) -> Self {
+        self.read_chunk_size = read_chunk_size;
+        self
+    }
+
+    /// Opt into recognizing `__stream_error__` sentinel objects on stdout
+    /// (see [`FallibleJsonStream`] and [`get_fallible_json_stream`]).
+    ///
+    /// [`get_fallible_json_stream`]: BinaryExecutor::get_fallible_json_stream
+    pub fn with_stream_error_parsing(mut self, enabled: bool) -> Self {
+        self.parse_stream_error = enabled;
+        self
+    }
+
+    /// Pull the `(message, code)` pair out of a decoded value if it's a
+    /// `{"__stream_error__": "...", "code": N}` sentinel, rather than a
+    /// normal event.
+    fn stream_error_sentinel(value: &serde_json::Value) -> Option<(String, Option<i64>)> {
+        let message = value.get("__stream_error__")?.as_str()?.to_string();
+        let code = value.get("code").and_then(|v| v.as_i64());
+        Some((message, code))
+    }
+
+    /// Build the `RunnerError` carried by a stream-error sentinel.
+    fn stream_error(message: String, code: Option<i64>) -> RunnerError {
+        let text = match code {
+            Some(code) => format!("runner reported a stream error (code {}): {}", code, message),
+            None => format!("runner reported a stream error: {}", message),
+        };
+        ClassifiedRunnerError::new(RunnerErrorKind::StreamError, text).boxed()
+    }
+
+    /// Spawn the binary, hand back its child handle and a framed JSON
+    /// decoder over its stdout, and start the background task that logs
+    /// stderr. Shared by [`get_json_stream`] and [`get_fallible_json_stream`].
+    ///
+    /// [`get_json_stream`]: BinaryExecutor::get_json_stream
+    /// [`get_fallible_json_stream`]: BinaryExecutor::get_fallible_json_stream
+    async fn spawn_framed(
+        &self,
+    ) -> Result<(tokio::process::Child, FramedRead<tokio::process::ChildStdout, JsonLineDecoder>), RunnerError> {
         // Log the actual exec command with all arguments
         if self.additional_args.is_empty() {
             log::info!("Executing binary: {}", self.binary_path);
         } else {
             log::info!("Executing binary: {} {}", self.binary_path, self.additional_args.join(" "));
         }
-        
+
         let mut cmd = TokioCommand::new(&self.binary_path);
         cmd.stdout(Stdio::piped())
            .stderr(Stdio::piped());
-        
+
         // Add additional arguments if any
         if !self.additional_args.is_empty() {
             cmd.args(&self.additional_args);
             debug!("Added arguments: {:?}", self.additional_args);
         }
-        
+
+        if !self.envs.is_empty() {
+            cmd.envs(&self.envs);
+            debug!("Added {} environment variable(s)", self.envs.len());
+        }
+
         let mut child = cmd.spawn()
-            .map_err(|e| Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other, 
-                format!("Failed to start binary: {}", e)
-            )) as RunnerError)?;
-            
+            .map_err(|e| {
+                let kind = classify_spawn_error(&e);
+                ClassifiedRunnerError::new(kind, format!("Failed to start binary: {}", e)).boxed()
+            })?;
+
         let stdout = child.stdout.take()
-            .ok_or_else(|| Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other, 
-                "Failed to get stdout"
-            )) as RunnerError)?;
-        
+            .ok_or_else(|| ClassifiedRunnerError::new(RunnerErrorKind::SpawnFailed, "Failed to get stdout").boxed())?;
+
         let stderr = child.stderr.take()
-            .ok_or_else(|| Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other, 
-                "Failed to get stderr"
-            )) as RunnerError)?;
-        
+            .ok_or_else(|| ClassifiedRunnerError::new(RunnerErrorKind::SpawnFailed, "Failed to get stderr").boxed())?;
+
         if let Some(pid) = child.id() {
             debug!("Binary started with PID: Some({})", pid);
         }
-        
+
         // Clone needed data for the stream
         let runner_name = self.runner_name.clone();
         let binary_path = self.binary_path.clone();
-        
+
         // Spawn a task to read and log stderr
         let stderr_runner_name = runner_name.clone();
         let stderr_binary_path = binary_path.clone();
         tokio::spawn(async move {
             let mut stderr_reader = BufReader::new(stderr);
             let mut stderr_line = String::new();
-            
+
             loop {
                 stderr_line.clear();
                 match stderr_reader.read_line(&mut stderr_line).await {
@@ -102,25 +433,20 @@ impl BinaryExecutor {
                     Ok(_) => {
                         let trimmed = stderr_line.trim();
                         if !trimmed.is_empty() {
-                            // Log stderr output as ERROR for visibility
                             let runner_info = stderr_runner_name.as_ref()
                                 .map(|name| format!("[{}] ", name))
-                                .unwrap_or_else(|| format!("[{}] ", 
+                                .unwrap_or_else(|| format!("[{}] ",
                                     std::path::Path::new(&stderr_binary_path)
                                         .file_name()
                                         .and_then(|n| n.to_str())
                                         .unwrap_or("unknown")
                                 ));
-                            
-                            // Check severity of the message
-                            if trimmed.contains("Failed") || trimmed.contains("Error") || 
-                               trimmed.contains("cannot") || trimmed.contains("permission denied") {
-                                log::error!("{}STDERR: {}", runner_info, trimmed);
-                            } else if trimmed.contains("warn") || trimmed.contains("Warning") {
-                                log::warn!("{}STDERR: {}", runner_info, trimmed);
-                            } else {
-                                log::info!("{}STDERR: {}", runner_info, trimmed);
-                            }
+
+                            // Traced binaries' stderr is free-form text, not a
+                            // structured signal - log it uniformly and let an
+                            // actual nonzero exit status (see `force_kill`)
+                            // drive severity instead of guessing from wording.
+                            log::info!("{}STDERR: {}", runner_info, trimmed);
                         }
                     }
                     Err(e) => {
@@ -133,181 +459,251 @@ impl BinaryExecutor {
             }
         });
 
+        let decoder = match self.max_frame_size {
+            Some(max) => JsonLineDecoder::with_max_frame_size(max),
+            None => JsonLineDecoder::new(),
+        };
+        let framed = FramedRead::with_capacity(stdout, decoder, self.read_chunk_size);
+        Ok((child, framed))
+    }
+
+    /// Send `signal` to the child's PID via a raw `kill(2)` call. A child
+    /// that has already exited (and so has no PID left to signal) is not
+    /// treated as an error.
+    fn send_signal(child: &tokio::process::Child, signal: libc::c_int) {
+        if let Some(pid) = child.id() {
+            let ret = unsafe { libc::kill(pid as libc::pid_t, signal) };
+            if ret != 0 {
+                let err = std::io::Error::last_os_error();
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!("Failed to send signal {} to PID {}: {}", signal, pid, err);
+                }
+            }
+        }
+    }
+
+    /// Escalate from SIGKILL only if the child is still alive; called once
+    /// the grace period given to SIGTERM (see the `get_json_stream`/
+    /// `get_fallible_json_stream` shutdown sequence) has elapsed. Returns the
+    /// child's exit status if it could be determined, for classification.
+    async fn force_kill(child: &mut tokio::process::Child) -> Option<std::process::ExitStatus> {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                debug!("Binary process exited on its own after SIGTERM with status: {}", status);
+                return Some(status);
+            }
+            Ok(None) => {
+                log::warn!("Binary process still running after shutdown grace period, sending SIGKILL");
+            }
+            Err(e) => {
+                log::warn!("Error checking binary process status: {}", e);
+            }
+        }
+
+        if let Err(e) = child.kill().await {
+            log::warn!("Failed to kill binary process: {}", e);
+        }
+        match child.wait().await {
+            Ok(status) => {
+                debug!("Binary process terminated with status: {}", status);
+                Some(status)
+            }
+            Err(e) => {
+                log::warn!("Error waiting for binary process: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Classify a terminated child's exit status as a [`ClassifiedRunnerError`]
+    /// if it didn't exit successfully.
+    fn classify_exit(status: std::process::ExitStatus) -> Option<ClassifiedRunnerError> {
+        if status.success() {
+            return None;
+        }
+        let code = status.code();
+        Some(ClassifiedRunnerError::new(
+            RunnerErrorKind::NonZeroExit { code },
+            format!("binary exited with status: {}", status),
+        ))
+    }
+
+    /// Execute binary and get raw JSON stream
+    pub async fn get_json_stream(&self) -> Result<JsonStream, RunnerError> {
+        let (mut child, mut framed) = self.spawn_framed().await?;
+        let parse_stream_error = self.parse_stream_error;
+        let shutdown_timeout = self.shutdown_timeout;
+
         let stream = async_stream::stream! {
-            let mut reader = BufReader::new(stdout);
-            let mut line = String::new();
-            let mut line_count = 0;
-            
+            let mut frame_count = 0;
+            let mut interrupted = false;
+
             debug!("Reading from binary stdout");
-            
+
             loop {
-                line.clear();
-                
-                match reader.read_line(&mut line).await {
-                    Ok(0) => {
-                        debug!("Binary stdout closed (EOF)");
-                        break;
-                    }
-                    Ok(_) => {
-                        line_count += 1;
-                        let trimmed = line.trim();
-                        
-                        if !trimmed.is_empty() {
-                            debug!("Line {}: {}", line_count, 
-                                if trimmed.len() > 100 { 
-                                    format!("{}...", &trimmed[..100]) 
-                                } else { 
-                                    trimmed.to_string() 
-                                }
-                            );
-                            
-                            // Try to parse as JSON
-                            if trimmed.starts_with('{') && trimmed.ends_with('}') {
-                                match serde_json::from_str::<serde_json::Value>(trimmed) {
-                                    Ok(json_value) => {
-                                        debug!("Parsed JSON value");
-                                        yield json_value;
-                                    }
-                                    Err(e) => {
-                                        log::warn!("Failed to parse JSON from line {}: {} - Line: {}", 
-                                            line_count, e,
-                                            if trimmed.len() > 200 { 
-                                                format!("{}...", &trimmed[..200]) 
-                                            } else { 
-                                                trimmed.to_string() 
-                                            }
+                tokio::select! {
+                    result = framed.next() => {
+                        match result {
+                            Some(Ok(value)) => {
+                                if parse_stream_error {
+                                    if let Some((message, code)) = Self::stream_error_sentinel(&value) {
+                                        log::warn!(
+                                            "Ignoring stream-error sentinel on the infallible JSON stream (code {:?}): {}",
+                                            code, message
                                         );
+                                        continue;
                                     }
                                 }
-                            } else {
-                                // Check if this might be a stderr message or debug output
-                                if trimmed.contains("error") || trimmed.contains("warn") || 
-                                   trimmed.contains("failed") || trimmed.contains("Error:") {
-                                    log::warn!("Possible error message from binary at line {}: {}", 
-                                        line_count, trimmed);
-                                } else {
-                                    log::warn!("Skipping non-JSON line {} from binary: {}", 
-                                        line_count, 
-                                        if trimmed.len() > 100 { 
-                                            format!("{}...", &trimmed[..100]) 
-                                        } else { 
-                                            trimmed.to_string() 
-                                        }
-                                    );
-                                }
+                                frame_count += 1;
+                                debug!("Parsed JSON frame {}", frame_count);
+                                yield value;
+                            }
+                            Some(Err(e)) => {
+                                log::warn!("Error decoding JSON stream from binary: {}", e);
                             }
+                            None => break,
                         }
                     }
-                    Err(e) => {
-                        // Handle UTF-8 errors gracefully - don't terminate, just warn and continue
-                        if e.kind() == std::io::ErrorKind::InvalidData {
-                            let runner_info = runner_name.as_ref()
-                                .map(|name| format!("[{}] ", name))
-                                .unwrap_or_else(|| format!("[{}] ", 
-                                    std::path::Path::new(&binary_path)
-                                        .file_name()
-                                        .and_then(|n| n.to_str())
-                                        .unwrap_or("unknown")
-                                ));
-                            
-                            // Try to recover partial data up to the invalid UTF-8 sequence
-                            let raw_bytes = line.as_bytes();
-                            let valid_up_to = String::from_utf8_lossy(raw_bytes);
-                            
-                            // If we have a partial JSON object, try to parse it
-                            if valid_up_to.trim_start().starts_with('{') {
-                                // Find the position of the invalid UTF-8
-                                let mut valid_len = 0;
-                                for i in 0..raw_bytes.len() {
-                                    if std::str::from_utf8(&raw_bytes[0..=i]).is_ok() {
-                                        valid_len = i + 1;
-                                    } else {
-                                        break;
-                                    }
-                                }
-                                
-                                if valid_len > 0 {
-                                    if let Ok(valid_str) = std::str::from_utf8(&raw_bytes[0..valid_len]) {
-                                        log::debug!("Recovered {} valid UTF-8 bytes before error", valid_len);
-                                        // Try to parse the valid portion
-                                        if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(valid_str.trim()) {
-                                            log::info!("Successfully recovered partial JSON despite UTF-8 error");
-                                            yield json_value;
-                                            continue;
-                                        }
-                                    }
+                    _ = tokio::signal::ctrl_c(), if !interrupted => {
+                        log::info!("Received Ctrl-C, forwarding termination to binary process");
+                        interrupted = true;
+                        Self::send_signal(&child, libc::SIGTERM);
+                    }
+                }
+            }
+
+            if !interrupted {
+                debug!("Binary stdout closed (EOF), read {} frames", frame_count);
+                Self::send_signal(&child, libc::SIGTERM);
+            }
+
+            // Give the process `shutdown_timeout` to detach probes and
+            // flush any buffered events on its own, draining whatever it
+            // still writes to stdout in the meantime, before escalating.
+            let drain_deadline = tokio::time::Instant::now() + shutdown_timeout;
+            loop {
+                tokio::select! {
+                    result = framed.next() => {
+                        match result {
+                            Some(Ok(value)) => {
+                                if parse_stream_error && Self::stream_error_sentinel(&value).is_some() {
+                                    continue;
                                 }
+                                frame_count += 1;
+                                yield value;
                             }
-                            
-                            // Log detailed error information
-                            let hex_preview = raw_bytes.iter()
-                                .take(64) // Show more context
-                                .map(|b| format!("{:02x}", b))
-                                .collect::<Vec<_>>()
-                                .join(" ");
-                            
-                            log::warn!(
-                                "{}Invalid UTF-8 at line {} (attempted recovery failed). Hex preview: {}",
-                                runner_info, line_count + 1, hex_preview
-                            );
-                            
-                            // Clear the line buffer and continue
-                            line.clear();
-                            continue;
-                        } else if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                            // Handle partial reads at EOF gracefully
-                            if !line.is_empty() {
-                                let trimmed = line.trim();
-                                if trimmed.starts_with('{') && trimmed.ends_with('}') {
-                                    // Try to parse incomplete JSON at EOF
-                                    match serde_json::from_str::<serde_json::Value>(trimmed) {
-                                        Ok(json_value) => {
-                                            log::debug!("Parsed final JSON line at EOF");
-                                            yield json_value;
-                                        }
-                                        Err(e) => {
-                                            log::warn!("Failed to parse final line at EOF: {}", e);
-                                        }
+                            Some(Err(_)) => {}
+                            None => break,
+                        }
+                    }
+                    _ = tokio::time::sleep_until(drain_deadline) => break,
+                }
+            }
+
+            debug!("Shutdown drain complete, read {} frames total", frame_count);
+            if let Some(status) = Self::force_kill(&mut child).await {
+                if let Some(err) = Self::classify_exit(status) {
+                    log::warn!("Binary process failed: {}", err);
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Execute binary and get a JSON stream that surfaces stream-error
+    /// sentinels (when [`with_stream_error_parsing`] is enabled) and
+    /// decode failures as a `RunnerError` instead of dropping them, so
+    /// callers can tell "the tracer hit a fatal error partway through" from
+    /// a clean EOF.
+    ///
+    /// [`with_stream_error_parsing`]: BinaryExecutor::with_stream_error_parsing
+    pub async fn get_fallible_json_stream(&self) -> Result<FallibleJsonStream, RunnerError> {
+        let (mut child, mut framed) = self.spawn_framed().await?;
+        let parse_stream_error = self.parse_stream_error;
+        let shutdown_timeout = self.shutdown_timeout;
+
+        let stream = async_stream::stream! {
+            let mut frame_count = 0;
+            let mut interrupted = false;
+
+            debug!("Reading from binary stdout");
+
+            loop {
+                tokio::select! {
+                    result = framed.next() => {
+                        match result {
+                            Some(Ok(value)) => {
+                                if parse_stream_error {
+                                    if let Some((message, code)) = Self::stream_error_sentinel(&value) {
+                                        log::warn!("Runner reported a stream error (code {:?}): {}", code, message);
+                                        yield Err(Self::stream_error(message, code));
+                                        continue;
                                     }
                                 }
+                                frame_count += 1;
+                                debug!("Parsed JSON frame {}", frame_count);
+                                yield Ok(value);
                             }
-                            log::debug!("Reached EOF while reading");
-                            break;
-                        } else if e.kind() == std::io::ErrorKind::Interrupted {
-                            // Retry on interrupted system calls
-                            log::debug!("Read interrupted, retrying...");
-                            continue;
-                        } else {
-                            log::warn!("Error reading from binary: {} (kind: {:?})", e, e.kind());
-                            break;
+                            Some(Err(e)) => {
+                                log::warn!("Error decoding JSON stream from binary: {}", e);
+                                yield Err(Box::new(e) as RunnerError);
+                            }
+                            None => break,
                         }
                     }
+                    _ = tokio::signal::ctrl_c(), if !interrupted => {
+                        log::info!("Received Ctrl-C, forwarding termination to binary process");
+                        interrupted = true;
+                        Self::send_signal(&child, libc::SIGTERM);
+                    }
                 }
             }
-            
-            log::info!("Terminating binary process");
-            
-            // Terminate the child process
-            if let Err(e) = child.kill().await {
-                log::warn!("Failed to kill binary process: {}", e);
+
+            if !interrupted {
+                debug!("Binary stdout closed (EOF), read {} frames", frame_count);
+                Self::send_signal(&child, libc::SIGTERM);
             }
-            
-            // Wait for process to finish
-            match child.wait().await {
-                Ok(status) => {
-                    debug!("Binary process terminated with status: {}", status);
+
+            // Give the process `shutdown_timeout` to detach probes and
+            // flush any buffered events on its own, draining whatever it
+            // still writes to stdout in the meantime, before escalating.
+            let drain_deadline = tokio::time::Instant::now() + shutdown_timeout;
+            loop {
+                tokio::select! {
+                    result = framed.next() => {
+                        match result {
+                            Some(Ok(value)) => {
+                                if parse_stream_error {
+                                    if let Some((message, code)) = Self::stream_error_sentinel(&value) {
+                                        yield Err(Self::stream_error(message, code));
+                                        continue;
+                                    }
+                                }
+                                frame_count += 1;
+                                yield Ok(value);
+                            }
+                            Some(Err(e)) => {
+                                yield Err(Box::new(e) as RunnerError);
+                            }
+                            None => break,
+                        }
+                    }
+                    _ = tokio::time::sleep_until(drain_deadline) => break,
                 }
-                Err(e) => {
-                    log::warn!("Error waiting for binary process: {}", e);
+            }
+
+            debug!("Shutdown drain complete, read {} frames total", frame_count);
+            if let Some(status) = Self::force_kill(&mut child).await {
+                if let Some(err) = Self::classify_exit(status) {
+                    log::warn!("Binary process failed: {}", err);
+                    yield Err(err.boxed());
                 }
             }
         };
-        
+
         Ok(Box::pin(stream))
     }
-
-
-
 }
 
 /// Common analyzer processor for runners
@@ -316,14 +712,494 @@ pub struct AnalyzerProcessor;
 impl AnalyzerProcessor {
     /// Process events through a chain of analyzers
     pub async fn process_through_analyzers(
-        mut stream: EventStream, 
+        mut stream: EventStream,
         analyzers: &mut [Box<dyn Analyzer>]
     ) -> Result<EventStream, RunnerError> {
         // Process through each analyzer in sequence
         for analyzer in analyzers.iter_mut() {
             stream = analyzer.process(stream).await?;
         }
-        
+
         Ok(stream)
     }
-} 
\ No newline at end of file
+
+    /// Flush every analyzer in the chain, on a best-effort basis: one
+    /// analyzer failing to flush doesn't stop the rest from being given the
+    /// chance, since a graceful shutdown should save as much buffered state
+    /// as it can rather than aborting partway through. The first error
+    /// encountered, if any, is returned after all analyzers have been tried.
+    pub async fn flush_analyzers(
+        analyzers: &mut [Box<dyn Analyzer>],
+    ) -> Result<(), RunnerError> {
+        let mut first_err: Option<RunnerError> = None;
+        for analyzer in analyzers.iter_mut() {
+            if let Err(e) = analyzer.flush().await {
+                log::warn!("Analyzer '{}' failed to flush: {}", analyzer.name(), e);
+                if first_err.is_none() {
+                    first_err = Some(e);
+                }
+            }
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// One entry in a hot-swappable [`AnalyzerPipeline`]: a stable id (so
+/// [`AnalyzerPipelineHandle::remove_analyzer`] can find it again) paired
+/// with the analyzer itself behind a `tokio::sync::Mutex`, since
+/// `Analyzer::process` takes `&mut self` but entries are reached through a
+/// shared `Arc`.
+type PipelineEntry = (u64, Arc<tokio::sync::Mutex<Box<dyn Analyzer>>>);
+
+/// A hot-swappable analyzer chain: unlike the static `Vec<Box<dyn
+/// Analyzer>>` [`AnalyzerProcessor`] processes once up front, a runner's
+/// `run()` loop re-loads the current chain from here for every event (a
+/// lock-free atomic pointer read via `ArcSwap::load`), so an operator
+/// holding this pipeline's [`AnalyzerPipelineHandle`] can push, remove, or
+/// wholesale replace analyzers - e.g. turning on a verbose redaction
+/// analyzer mid-incident - without tearing down the runner or restarting
+/// the underlying eBPF collection.
+pub struct AnalyzerPipeline {
+    entries: Arc<ArcSwap<Vec<PipelineEntry>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl AnalyzerPipeline {
+    /// Seed the pipeline with the analyzers a runner was constructed with
+    /// (e.g. via the old consuming `add_analyzer` builder).
+    pub fn new(initial: Vec<Box<dyn Analyzer>>) -> Self {
+        let next_id = Arc::new(AtomicU64::new(0));
+        let entries: Vec<PipelineEntry> = initial
+            .into_iter()
+            .map(|analyzer| {
+                let id = next_id.fetch_add(1, Ordering::SeqCst);
+                (id, Arc::new(tokio::sync::Mutex::new(analyzer)))
+            })
+            .collect();
+        Self {
+            entries: Arc::new(ArcSwap::from_pointee(entries)),
+            next_id,
+        }
+    }
+
+    /// A cloneable handle an operator can use to mutate this pipeline's
+    /// chain from outside the runner while it streams.
+    pub fn handle(&self) -> AnalyzerPipelineHandle {
+        AnalyzerPipelineHandle {
+            entries: Arc::clone(&self.entries),
+            next_id: Arc::clone(&self.next_id),
+        }
+    }
+
+    /// Run one event through the current analyzer snapshot in order. Each
+    /// analyzer sees the whole in-flight batch through the usual
+    /// stream-shaped `process` call (wrapped as a single-item stream), so
+    /// filtering/fan-out analyzers behave exactly as they would in a static
+    /// chain; every event that survives the whole chain is returned.
+    pub async fn process_event(&self, event: Event) -> Vec<Event> {
+        let snapshot = self.entries.load_full();
+        let mut events = vec![event];
+        for (_, analyzer) in snapshot.iter() {
+            if events.is_empty() {
+                break;
+            }
+            let mut next = Vec::new();
+            for ev in events {
+                let single: EventStream = Box::pin(futures::stream::once(futures::future::ready(ev)));
+                let mut guard = analyzer.lock().await;
+                match guard.process(single).await {
+                    Ok(stream) => next.extend(stream.collect::<Vec<_>>().await),
+                    Err(e) => {
+                        log::warn!("Analyzer '{}' failed to process event, dropping it: {}", guard.name(), e);
+                    }
+                }
+            }
+            events = next;
+        }
+        events
+    }
+
+    /// Flush every analyzer in the current snapshot, best-effort (see
+    /// [`AnalyzerProcessor::flush_analyzers`]).
+    pub async fn flush(&self) -> Result<(), RunnerError> {
+        let snapshot = self.entries.load_full();
+        let mut first_err: Option<RunnerError> = None;
+        for (_, analyzer) in snapshot.iter() {
+            let mut guard = analyzer.lock().await;
+            if let Err(e) = guard.flush().await {
+                log::warn!("Analyzer '{}' failed to flush: {}", guard.name(), e);
+                if first_err.is_none() {
+                    first_err = Some(e);
+                }
+            }
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Handle for mutating a running [`AnalyzerPipeline`]'s analyzer chain from
+/// outside the runner that owns it.
+#[derive(Clone)]
+pub struct AnalyzerPipelineHandle {
+    entries: Arc<ArcSwap<Vec<PipelineEntry>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl AnalyzerPipelineHandle {
+    /// Append an analyzer to the end of the chain, returning the id it was
+    /// assigned - pass it to [`remove_analyzer`](Self::remove_analyzer)
+    /// later to drop it again.
+    pub fn push_analyzer(&self, analyzer: Box<dyn Analyzer>) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let entry: PipelineEntry = (id, Arc::new(tokio::sync::Mutex::new(analyzer)));
+        self.entries.rcu(move |current| {
+            let mut next = current.clone();
+            next.push(entry.clone());
+            next
+        });
+        id
+    }
+
+    /// Drop the analyzer previously assigned `id`; a no-op if it's already
+    /// gone.
+    pub fn remove_analyzer(&self, id: u64) {
+        self.entries.rcu(move |current| {
+            current
+                .iter()
+                .filter(|(entry_id, _)| *entry_id != id)
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+    }
+
+    /// Atomically swap out the entire chain for `analyzers`, each assigned a
+    /// fresh id.
+    pub fn replace_analyzers(&self, analyzers: Vec<Box<dyn Analyzer>>) {
+        let next: Vec<PipelineEntry> = analyzers
+            .into_iter()
+            .map(|analyzer| {
+                let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+                (id, Arc::new(tokio::sync::Mutex::new(analyzer)))
+            })
+            .collect();
+        self.entries.store(Arc::new(next));
+    }
+}
+
+/// Number of events a runner batches up before firing its `events_batch`
+/// USDT probe - matches the granularity bpftrace scripts/integration tests
+/// actually care about (throughput, not per-event noise).
+const PROBE_BATCH_SIZE: u64 = 32;
+
+/// Fire the `agentsight:events_batch` USDT probe for a completed (or final
+/// partial) batch of emitted events, tagged with the runner's name so a
+/// bpftrace script or integration test can tell which runner it came from.
+/// A no-op for an empty batch, e.g. flushing a tracker that never emitted
+/// anything.
+fn fire_events_batch_probe(runner_name: &str, batch_len: u64) {
+    if batch_len == 0 {
+        return;
+    }
+    if let Ok(name) = std::ffi::CString::new(runner_name) {
+        probe::probe!(agentsight, events_batch, name.as_ptr(), batch_len);
+    }
+}
+
+/// Owns the sending half of a runner's [`RunnerProgress`] `watch` channel,
+/// so a runner only has to hold one field and call a few helper methods
+/// instead of hand-rolling `watch::Sender::send_modify` calls at every
+/// state transition. Shared the same way [`AnalyzerProcessor`] is shared by
+/// every runner that owns an analyzer chain.
+///
+/// Also batches up emitted events to fire the `agentsight:events_batch` USDT
+/// probe every [`PROBE_BATCH_SIZE`] events, so bpftrace scripts and
+/// integration tests can synchronize on a runner's real throughput without
+/// polling its `watch` channel.
+pub struct RunnerProgressTracker {
+    sender: watch::Sender<RunnerProgress>,
+    runner_name: String,
+    events_since_probe: AtomicU64,
+}
+
+impl RunnerProgressTracker {
+    /// Create a tracker starting in [`RunnerState::Starting`], tagging its
+    /// USDT probe firings with `runner_name` (e.g. `"system"`, `"sched"`).
+    pub fn new(runner_name: impl Into<String>) -> Self {
+        let (sender, _receiver) = watch::channel(RunnerProgress {
+            state: RunnerState::Starting,
+            ..RunnerProgress::default()
+        });
+        Self {
+            sender,
+            runner_name: runner_name.into(),
+            events_since_probe: AtomicU64::new(0),
+        }
+    }
+
+    /// Subscribe to this runner's progress, for [`Runner::progress`].
+    pub fn receiver(&self) -> watch::Receiver<RunnerProgress> {
+        self.sender.subscribe()
+    }
+
+    /// Mark the runner as live; called once its `EventStream` is ready to
+    /// yield events.
+    pub fn mark_running(&self) {
+        self.sender.send_modify(|progress| progress.state = RunnerState::Running);
+    }
+
+    /// Mark the runner as degraded, e.g. after a recoverable parse error,
+    /// without losing the counters tallied so far.
+    #[allow(dead_code)]
+    pub fn mark_degraded(&self, reason: impl Into<String>) {
+        self.sender.send_modify(|progress| progress.state = RunnerState::Degraded(reason.into()));
+    }
+
+    /// Mark the runner's stream as ended, also flushing any partial batch of
+    /// events that hasn't yet reached [`PROBE_BATCH_SIZE`] through the USDT
+    /// probe so a watcher sees the runner's true final count.
+    pub fn mark_stopped(&self) {
+        self.sender.send_modify(|progress| progress.state = RunnerState::Stopped);
+        let remaining = self.events_since_probe.swap(0, Ordering::SeqCst);
+        fire_events_batch_probe(&self.runner_name, remaining);
+    }
+
+    /// Tally one emitted event: bumps `events_emitted`/`bytes_read` (the
+    /// latter approximated from the event's serialized JSON length) and
+    /// updates `last_event_ts`. Also counts the event towards this runner's
+    /// next `events_batch` USDT probe firing.
+    pub fn record_event(&self, event: &Event) {
+        let bytes = event.to_json().map(|s| s.len() as u64).unwrap_or(0);
+        self.sender.send_modify(|progress| {
+            progress.events_emitted += 1;
+            progress.bytes_read += bytes;
+            progress.last_event_ts = Some(event.timestamp);
+        });
+
+        if self.events_since_probe.fetch_add(1, Ordering::SeqCst) + 1 >= PROBE_BATCH_SIZE {
+            let batch_len = self.events_since_probe.swap(0, Ordering::SeqCst);
+            fire_events_batch_probe(&self.runner_name, batch_len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod progress_tracker_tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_event() -> Event {
+        Event::new_with_timestamp(0, "system".to_string(), 0, "test".to_string(), json!({}))
+    }
+
+    #[test]
+    fn test_record_event_resets_probe_counter_at_batch_size() {
+        let tracker = RunnerProgressTracker::new("system");
+
+        for _ in 0..PROBE_BATCH_SIZE - 1 {
+            tracker.record_event(&test_event());
+        }
+        assert_eq!(tracker.events_since_probe.load(Ordering::SeqCst), PROBE_BATCH_SIZE - 1);
+
+        tracker.record_event(&test_event());
+        assert_eq!(tracker.events_since_probe.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_mark_stopped_flushes_a_partial_batch() {
+        let tracker = RunnerProgressTracker::new("system");
+        tracker.record_event(&test_event());
+        tracker.record_event(&test_event());
+
+        tracker.mark_stopped();
+        assert_eq!(tracker.events_since_probe.load(Ordering::SeqCst), 0);
+    }
+}
+
+/// How often [`with_hash_stream`] emits a checkpoint digest event: every
+/// `n` events, or every fixed wall-clock duration - whichever boundary the
+/// caller's durability model calls for (e.g. "checkpoint every 10k events"
+/// vs. "checkpoint at least once a minute even if traffic is quiet").
+#[derive(Debug, Clone, Copy)]
+pub enum HashCheckpointBoundary {
+    EveryEvents(u64),
+    EveryDuration(Duration),
+}
+
+/// Observes the running SHA3-256 digest a [`with_hash_stream`]-wrapped
+/// stream is accumulating, from outside the stream's own consumer.
+pub struct HashHandle {
+    receiver: watch::Receiver<String>,
+}
+
+impl HashHandle {
+    /// The most recent checkpoint digest (lowercase hex), or an empty
+    /// string before the first checkpoint has been produced.
+    pub fn current_digest(&self) -> String {
+        self.receiver.borrow().clone()
+    }
+
+    /// Wait for the next checkpoint and return its digest, or `None` once
+    /// the wrapped stream (and its sender) has been dropped.
+    pub async fn next_digest(&mut self) -> Option<String> {
+        self.receiver.changed().await.ok()?;
+        Some(self.receiver.borrow().clone())
+    }
+}
+
+fn hex_encode(bytes: impl AsRef<[u8]>) -> String {
+    bytes.as_ref().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Build the checkpoint event [`with_hash_stream`] emits at each boundary.
+fn make_hash_checkpoint_event(digest: &str, events_since_last: u64) -> Event {
+    let timestamp = crate::framework::core::timestamp::now_epoch_ms();
+    Event::new_with_timestamp(
+        timestamp,
+        "hash_checkpoint".to_string(),
+        0,
+        "integrity".to_string(),
+        serde_json::json!({
+            "type": "hash_checkpoint",
+            "algorithm": "sha3-256",
+            "digest": digest,
+            "events_since_last_checkpoint": events_since_last,
+        }),
+    )
+}
+
+/// Wrap `stream` so every event passing through is fed - as its canonical
+/// JSON encoding (see [`Event::to_json`]), not the in-memory struct - into
+/// a running SHA3-256 hasher, and a checkpoint `Event` carrying the digest
+/// accumulated so far is emitted at `boundary` (and once more when the
+/// stream ends), so downstream storage can prove a captured
+/// security-observability log was not truncated or reordered in transit.
+///
+/// Transparent to the wrapped stream's own events: every input event is
+/// passed through unchanged and in order, with checkpoint events
+/// interleaved after the event that crossed the boundary.
+pub fn with_hash_stream(stream: EventStream, boundary: HashCheckpointBoundary) -> (EventStream, HashHandle) {
+    let (sender, receiver) = watch::channel(String::new());
+
+    enum Next {
+        Event(Option<Event>),
+        Tick,
+    }
+
+    let hashed = async_stream::stream! {
+        let mut hasher = Sha3_256::new();
+        let mut events_since_checkpoint: u64 = 0;
+        let mut ticker = match boundary {
+            HashCheckpointBoundary::EveryDuration(d) => Some(tokio::time::interval(d)),
+            HashCheckpointBoundary::EveryEvents(_) => None,
+        };
+        tokio::pin!(stream);
+
+        loop {
+            let next = match ticker.as_mut() {
+                Some(ticker) => tokio::select! {
+                    event = stream.next() => Next::Event(event),
+                    _ = ticker.tick() => Next::Tick,
+                },
+                None => Next::Event(stream.next().await),
+            };
+
+            match next {
+                Next::Event(Some(event)) => {
+                    if let Some(json) = event.to_json() {
+                        hasher.update(json.as_bytes());
+                    }
+                    events_since_checkpoint += 1;
+                    yield event;
+
+                    if let HashCheckpointBoundary::EveryEvents(n) = boundary {
+                        if events_since_checkpoint >= n {
+                            let digest = hex_encode(hasher.clone().finalize());
+                            let _ = sender.send(digest.clone());
+                            yield make_hash_checkpoint_event(&digest, events_since_checkpoint);
+                            events_since_checkpoint = 0;
+                        }
+                    }
+                }
+                Next::Event(None) => {
+                    let digest = hex_encode(hasher.clone().finalize());
+                    let _ = sender.send(digest.clone());
+                    yield make_hash_checkpoint_event(&digest, events_since_checkpoint);
+                    break;
+                }
+                Next::Tick => {
+                    let digest = hex_encode(hasher.clone().finalize());
+                    let _ = sender.send(digest.clone());
+                    yield make_hash_checkpoint_event(&digest, events_since_checkpoint);
+                    events_since_checkpoint = 0;
+                }
+            }
+        }
+    };
+
+    (Box::pin(hashed), HashHandle { receiver })
+}
+
+#[cfg(test)]
+mod hash_stream_tests {
+    use super::*;
+    use futures::stream;
+
+    fn test_event(n: u32) -> Event {
+        Event::new_with_timestamp(n as u64, "test".to_string(), 0, "test".to_string(), serde_json::json!({"n": n}))
+    }
+
+    #[tokio::test]
+    async fn test_with_hash_stream_passes_events_through_unchanged_and_in_order() {
+        let events: EventStream = Box::pin(stream::iter((0..5).map(test_event)));
+        let (hashed, _handle) = with_hash_stream(events, HashCheckpointBoundary::EveryEvents(2));
+
+        let collected: Vec<_> = hashed.collect().await;
+        let passthrough: Vec<u32> = collected.iter()
+            .filter(|e| e.source == "test")
+            .filter_map(|e| e.data.get("n").and_then(|v| v.as_u64()).map(|n| n as u32))
+            .collect();
+
+        assert_eq!(passthrough, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_with_hash_stream_emits_checkpoint_every_n_events() {
+        let events: EventStream = Box::pin(stream::iter((0..4).map(test_event)));
+        let (hashed, _handle) = with_hash_stream(events, HashCheckpointBoundary::EveryEvents(2));
+
+        let collected: Vec<_> = hashed.collect().await;
+        let checkpoints = collected.iter().filter(|e| e.source == "hash_checkpoint").count();
+
+        // One checkpoint every 2 events (4 events -> 2 mid-stream
+        // checkpoints) plus the final one emitted at stream end.
+        assert_eq!(checkpoints, 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_hash_stream_handle_reports_matching_final_digest() {
+        let events: EventStream = Box::pin(stream::iter((0..3).map(test_event)));
+        let (hashed, mut handle) = with_hash_stream(events, HashCheckpointBoundary::EveryEvents(1));
+
+        let collected: Vec<_> = hashed.collect().await;
+        let last_checkpoint_digest = collected.iter()
+            .filter(|e| e.source == "hash_checkpoint")
+            .last()
+            .and_then(|e| e.data.get("digest").and_then(|v| v.as_str()))
+            .unwrap()
+            .to_string();
+
+        // Drain the handle to its final reported digest.
+        let mut last_seen = handle.current_digest();
+        while let Some(digest) = handle.next_digest().await {
+            last_seen = digest;
+        }
+
+        assert_eq!(last_seen, last_checkpoint_digest);
+    }
+}
\ No newline at end of file
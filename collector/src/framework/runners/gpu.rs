@@ -0,0 +1,312 @@
+use super::common::RunnerProgressTracker;
+use super::{EventStream, Runner, RunnerError, RunnerProgress};
+use crate::framework::analyzers::Analyzer;
+use crate::framework::core::Event;
+use async_trait::async_trait;
+use futures::stream::{Stream, StreamExt};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+
+/// Accelerator vendor, identified from the PCI vendor ID in
+/// `/sys/bus/pci/devices/<addr>/vendor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+    Unknown,
+}
+
+impl GpuVendor {
+    fn from_pci_vendor_id(id: &str) -> Self {
+        match id.trim_start_matches("0x").to_ascii_lowercase().as_str() {
+            "10de" => GpuVendor::Nvidia,
+            "1002" => GpuVendor::Amd,
+            "8086" => GpuVendor::Intel,
+            _ => GpuVendor::Unknown,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            GpuVendor::Nvidia => "nvidia",
+            GpuVendor::Amd => "amd",
+            GpuVendor::Intel => "intel",
+            GpuVendor::Unknown => "unknown",
+        }
+    }
+}
+
+/// One enumerated accelerator: its PCI address, vendor, and the sysfs
+/// directory samples are read from.
+#[derive(Debug, Clone)]
+struct GpuDevice {
+    pci_address: String,
+    vendor: GpuVendor,
+    sysfs_path: PathBuf,
+}
+
+/// PCI device class for a display controller (the top byte of the 24-bit
+/// class code at `/sys/bus/pci/devices/<addr>/class`); covers VGA (0x0300),
+/// 3D (0x0302), and other display-controller subclasses alike.
+const PCI_DISPLAY_CONTROLLER_CLASS_PREFIX: &str = "0x03";
+
+/// Enumerate accelerators by scanning `/sys/bus/pci/devices` for display
+/// controllers, the same PCI-config-space approach `lspci`/`nvidia-smi -L`
+/// use under the hood, so this works without any vendor management library
+/// installed.
+fn enumerate_gpu_devices() -> Vec<GpuDevice> {
+    let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+        return Vec::new();
+    };
+
+    let mut devices = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(class) = fs::read_to_string(path.join("class")) else {
+            continue;
+        };
+        if !class.trim().starts_with(PCI_DISPLAY_CONTROLLER_CLASS_PREFIX) {
+            continue;
+        }
+
+        let vendor = fs::read_to_string(path.join("vendor"))
+            .map(|v| GpuVendor::from_pci_vendor_id(v.trim()))
+            .unwrap_or(GpuVendor::Unknown);
+
+        let pci_address = entry.file_name().to_string_lossy().to_string();
+        devices.push(GpuDevice { pci_address, vendor, sysfs_path: path });
+    }
+
+    devices
+}
+
+/// Configuration for [`GpuRunner`].
+#[derive(Debug, Clone)]
+pub struct GpuConfig {
+    /// How often to poll every enumerated device.
+    pub poll_interval: Duration,
+    /// Only sample devices whose PCI address is in this list. `None` means
+    /// every enumerated device.
+    pub device_filter: Option<Vec<String>>,
+}
+
+impl Default for GpuConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(5),
+            device_filter: None,
+        }
+    }
+}
+
+/// Read the first hwmon sample under `device/hwmon/hwmon*/<file>` for a
+/// device's sysfs directory, the same hwmon layout
+/// [`SystemRunner`](super::SystemRunner) already reads for CPU thermal
+/// sensors - GPUs expose temperature/power the same way.
+fn read_first_hwmon_value(device_path: &Path, file: &str) -> Option<f64> {
+    let hwmon_dir = device_path.join("hwmon");
+    let entries = fs::read_dir(hwmon_dir).ok()?;
+    for entry in entries.flatten() {
+        if let Ok(raw) = fs::read_to_string(entry.path().join(file)) {
+            if let Ok(value) = raw.trim().parse::<f64>() {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+/// Sample one device's utilization/memory/temperature/power, preferring the
+/// vendor-specific sysfs attributes `amdgpu`/`i915` publish directly and
+/// falling back to hwmon-only readings (so at minimum device-presence and
+/// memory events are produced) when a vendor management library like NVML
+/// isn't linked in - this crate has no NVML binding, so NVIDIA devices
+/// always take the hwmon-only path today.
+fn sample_gpu_device(device: &GpuDevice, timestamp: u64) -> Event {
+    let busy_percent = fs::read_to_string(device.sysfs_path.join("gpu_busy_percent"))
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok());
+
+    let mem_used_bytes = fs::read_to_string(device.sysfs_path.join("mem_info_vram_used"))
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok());
+    let mem_total_bytes = fs::read_to_string(device.sysfs_path.join("mem_info_vram_total"))
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok());
+
+    // hwmon reports milli-Celsius / microwatts; normalize to degrees C and
+    // watts to match the units `read_hwmon_sensors` reports elsewhere.
+    let temp_c = read_first_hwmon_value(&device.sysfs_path, "temp1_input").map(|v| v / 1000.0);
+    let power_watts = read_first_hwmon_value(&device.sysfs_path, "power1_average")
+        .or_else(|| read_first_hwmon_value(&device.sysfs_path, "power1_input"))
+        .map(|v| v / 1_000_000.0);
+
+    let payload = json!({
+        "type": "gpu",
+        "timestamp": timestamp,
+        "pci_address": device.pci_address,
+        "vendor": device.vendor.as_str(),
+        "utilization_percent": busy_percent,
+        "memory_used_bytes": mem_used_bytes,
+        "memory_total_bytes": mem_total_bytes,
+        "temperature_c": temp_c,
+        "power_watts": power_watts,
+    });
+
+    Event::new_with_timestamp(timestamp, "gpu".to_string(), 0, device.pci_address.clone(), payload)
+}
+
+/// Get nanoseconds since boot (matching bpf_ktime_get_ns() behavior), the
+/// same convention [`SystemRunner`](super::SystemRunner) uses for its
+/// sample timestamps.
+fn get_boot_time_ns() -> u64 {
+    fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|s| s.split_whitespace().next().map(str::to_string))
+        .and_then(|secs| secs.parse::<f64>().ok())
+        .map(|secs| (secs * 1_000_000_000.0) as u64)
+        .unwrap_or(0)
+}
+
+fn create_gpu_event_stream(config: GpuConfig) -> Pin<Box<dyn Stream<Item = Event> + Send>> {
+    Box::pin(async_stream::stream! {
+        let mut interval = time::interval(config.poll_interval);
+
+        loop {
+            interval.tick().await;
+
+            let devices = enumerate_gpu_devices();
+            let timestamp = get_boot_time_ns();
+
+            for device in &devices {
+                if let Some(filter) = &config.device_filter {
+                    if !filter.contains(&device.pci_address) {
+                        continue;
+                    }
+                }
+                yield sample_gpu_device(device, timestamp);
+            }
+        }
+    })
+}
+
+/// Runner for accelerator (GPU) utilization/memory/thermal/power telemetry.
+///
+/// Devices are (re-)enumerated from PCI config space every poll rather than
+/// once at startup, so a GPU that's hot-added/removed (or simply not yet
+/// bound to its driver at process start) is picked up without a restart.
+pub struct GpuRunner {
+    config: GpuConfig,
+    analyzers: Vec<Box<dyn Analyzer>>,
+    progress: Arc<RunnerProgressTracker>,
+}
+
+impl GpuRunner {
+    /// Create a new GPU runner with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: GpuConfig::default(),
+            analyzers: Vec::new(),
+            progress: Arc::new(RunnerProgressTracker::new("gpu")),
+        }
+    }
+
+    /// Set how often every enumerated device is polled.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.config.poll_interval = interval;
+        self
+    }
+
+    /// Only sample devices whose PCI address is in `addresses`.
+    pub fn device_filter(mut self, addresses: Vec<String>) -> Self {
+        self.config.device_filter = Some(addresses);
+        self
+    }
+}
+
+impl Default for GpuRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Runner for GpuRunner {
+    async fn run(&mut self) -> Result<EventStream, RunnerError> {
+        let config = self.config.clone();
+        let stream = create_gpu_event_stream(config);
+
+        let event_stream = super::common::AnalyzerProcessor::process_through_analyzers(
+            Box::pin(stream),
+            &mut self.analyzers,
+        )
+        .await?;
+
+        self.progress.mark_running();
+        let progress = Arc::clone(&self.progress);
+        let tracked_stream = event_stream.inspect(move |event| progress.record_event(event));
+
+        Ok(Box::pin(tracked_stream))
+    }
+
+    async fn flush(&mut self) -> Result<(), RunnerError> {
+        self.progress.mark_stopped();
+        super::common::AnalyzerProcessor::flush_analyzers(&mut self.analyzers).await
+    }
+
+    fn progress(&self) -> tokio::sync::watch::Receiver<RunnerProgress> {
+        self.progress.receiver()
+    }
+
+    fn add_analyzer(mut self, analyzer: Box<dyn Analyzer>) -> Self {
+        self.analyzers.push(analyzer);
+        self
+    }
+
+    fn name(&self) -> &str {
+        "gpu"
+    }
+
+    fn id(&self) -> String {
+        "gpu".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_vendor_from_pci_vendor_id() {
+        assert_eq!(GpuVendor::from_pci_vendor_id("0x10de"), GpuVendor::Nvidia);
+        assert_eq!(GpuVendor::from_pci_vendor_id("0x1002"), GpuVendor::Amd);
+        assert_eq!(GpuVendor::from_pci_vendor_id("0x8086"), GpuVendor::Intel);
+        assert_eq!(GpuVendor::from_pci_vendor_id("0xdead"), GpuVendor::Unknown);
+    }
+
+    #[test]
+    fn test_enumerate_gpu_devices_does_not_panic_without_a_gpu() {
+        // Sandboxes/CI hosts commonly have no display-class PCI device at
+        // all; this is a smoke test that scanning never panics and that any
+        // device found does carry a non-empty PCI address.
+        for device in enumerate_gpu_devices() {
+            assert!(!device.pci_address.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_gpu_runner_builder_sets_poll_interval_and_filter() {
+        let runner = GpuRunner::new()
+            .poll_interval(Duration::from_secs(2))
+            .device_filter(vec!["0000:01:00.0".to_string()]);
+
+        assert_eq!(runner.config.poll_interval, Duration::from_secs(2));
+        assert_eq!(runner.config.device_filter, Some(vec!["0000:01:00.0".to_string()]));
+    }
+}
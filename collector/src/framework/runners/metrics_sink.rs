@@ -0,0 +1,88 @@
+use crate::framework::analyzers::render_global_prometheus_metrics;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+/// Serves the crate-wide Prometheus/OpenMetrics text collected by
+/// [`MetricsCollector`](crate::framework::analyzers::MetricsCollector) and
+/// every `Analyzer` that reports through
+/// [`record_analyzer_process`](crate::framework::analyzers::record_analyzer_process)/
+/// [`record_filter_match`](crate::framework::analyzers::record_filter_match)
+/// over a `/metrics` HTTP endpoint - the same standalone-hyper-server
+/// approach [`HttpSseRunner`](super::HttpSseRunner) uses to serve the merged
+/// event stream, since there's no shared route table to add a route to yet.
+/// Unlike `HttpSseRunner` this doesn't consume an `EventStream`: the
+/// counters already live in the global registry, so each request just
+/// renders its current snapshot.
+pub struct MetricsSink {
+    addr: SocketAddr,
+}
+
+impl MetricsSink {
+    /// Create a new metrics sink bound to the given address (e.g. `127.0.0.1:9090`)
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+
+    /// Serve `/metrics` until the process exits or the server errors.
+    pub async fn serve(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let make_svc = make_service_fn(|_conn| async {
+            Ok::<_, Infallible>(service_fn(|req: Request<Body>| async move {
+                Ok::<_, Infallible>(handle_request(req))
+            }))
+        });
+
+        let server = Server::bind(&self.addr).serve(make_svc);
+        log::info!("MetricsSink listening on {}", self.addr);
+
+        if let Err(e) = server.await {
+            log::error!("MetricsSink server error: {}", e);
+        }
+
+        Ok(())
+    }
+}
+
+fn handle_request(req: Request<Body>) -> Response<Body> {
+    if req.uri().path() != "/metrics" {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("not found"))
+            .unwrap();
+    }
+
+    Response::builder()
+        .header("content-type", "text/plain; version=0.0.4")
+        .body(Body::from(render_global_prometheus_metrics()))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_sink_defaults() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let sink = MetricsSink::new(addr);
+        assert_eq!(sink.addr, addr);
+    }
+
+    #[test]
+    fn test_handle_request_serves_metrics_at_the_metrics_path() {
+        let req = Request::builder().uri("/metrics").body(Body::empty()).unwrap();
+        let resp = handle_request(req);
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/plain; version=0.0.4");
+    }
+
+    #[test]
+    fn test_handle_request_404s_on_other_paths() {
+        let req = Request::builder().uri("/other").body(Body::empty()).unwrap();
+        let resp = handle_request(req);
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -2,6 +2,7 @@ use crate::framework::core::Event;
 use async_trait::async_trait;
 use futures::stream::Stream;
 use std::pin::Pin;
+use tokio::sync::watch;
 
 /// Type alias for event streams
 pub type EventStream = Pin<Box<dyn Stream<Item = Event> + Send>>;
@@ -9,21 +10,84 @@ pub type EventStream = Pin<Box<dyn Stream<Item = Event> + Send>>;
 /// Type alias for errors that can be sent between threads
 pub type RunnerError = Box<dyn std::error::Error + Send + Sync>;
 
+/// Lifecycle state reported in a [`RunnerProgress`] snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunnerState {
+    /// `run()` has been called but hasn't produced its event stream yet.
+    Starting,
+    /// The stream is live and (at least potentially) emitting events.
+    Running,
+    /// Still running, but something recoverable went wrong (e.g. a
+    /// malformed frame was dropped); the message is human-readable context.
+    Degraded(String),
+    /// The stream has ended or the runner was never started.
+    Stopped,
+}
+
+/// Snapshot of a running collector's health/throughput, broadcast over a
+/// `watch` channel (see [`Runner::progress`]) so an observer always sees the
+/// latest state rather than a queue of history it has to drain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunnerProgress {
+    pub state: RunnerState,
+    pub events_emitted: u64,
+    pub bytes_read: u64,
+    pub last_event_ts: Option<u64>,
+}
+
+impl Default for RunnerProgress {
+    fn default() -> Self {
+        Self {
+            state: RunnerState::Stopped,
+            events_emitted: 0,
+            bytes_read: 0,
+            last_event_ts: None,
+        }
+    }
+}
+
 /// Base trait for all runners that collect observability data
 #[async_trait]
 pub trait Runner: Send + Sync {
     /// Run the data collection and return a stream of events
     async fn run(&mut self) -> Result<EventStream, RunnerError>;
-    
+
+    /// Flush any analyzers in this runner's own chain before shutdown.
+    ///
+    /// The default is a no-op, which is what runners with no analyzer chain
+    /// of their own (or none yet converted to support graceful shutdown)
+    /// get for free. Runners that own an analyzer chain (e.g.
+    /// [`SystemRunner`](super::SystemRunner), [`SchedRunner`](super::SchedRunner),
+    /// [`AgentRunner`](super::AgentRunner)) override this to flush it.
+    async fn flush(&mut self) -> Result<(), RunnerError> {
+        Ok(())
+    }
+
+    /// Observe this runner's live state/throughput without draining its
+    /// `EventStream`.
+    ///
+    /// The default hands back an already-[`Stopped`](RunnerState::Stopped)
+    /// snapshot from a channel nothing will ever update - what a runner with
+    /// no progress tracking of its own gets for free, the same way `flush`'s
+    /// default is a no-op. Runners that track real progress (e.g.
+    /// [`SystemRunner`](super::SystemRunner), [`SchedRunner`](super::SchedRunner),
+    /// [`AgentRunner`](super::AgentRunner)) keep a
+    /// [`common::RunnerProgressTracker`](super::common::RunnerProgressTracker)
+    /// and override this to return its receiver.
+    fn progress(&self) -> watch::Receiver<RunnerProgress> {
+        let (_tx, rx) = watch::channel(RunnerProgress::default());
+        rx
+    }
+
     /// Add an analyzer to this runner's processing chain
     fn add_analyzer(self, analyzer: Box<dyn crate::framework::analyzers::Analyzer>) -> Self
     where
         Self: Sized;
-    
+
     /// Get the name of this runner
     #[allow(dead_code)]
     fn name(&self) -> &str;
-    
+
     /// Get a unique identifier for this runner instance
     #[allow(dead_code)]
     fn id(&self) -> String;
@@ -68,9 +132,24 @@ pub mod process;
 pub mod fake; // Add fake runner for testing
 pub mod agent; // Add agent runner for flexible composition
 pub mod system; // Add system runner for CPU and memory monitoring
+pub mod sched; // Add sched runner for off-CPU/scheduling-latency tracing
+pub mod gpu; // Add GPU runner for accelerator utilization/memory/thermal/power monitoring
+pub mod sse_sink; // Add HTTP SSE sink for streaming merged events out
+pub mod remote_sink; // Add authenticated/encrypted sink for remote collectors
+pub mod registry; // Add config-driven registry for externally-declared tracer binaries
+pub mod collector; // Add predicate-based collector for ad hoc event subscriptions
+pub mod metrics_sink; // Add HTTP /metrics sink for the crate-wide Prometheus registry
 
 pub use ssl::SslRunner;
 pub use process::ProcessRunner;
 pub use fake::FakeRunner; // Export FakeRunner
 pub use agent::AgentRunner; // Export AgentRunner
-pub use system::SystemRunner; // Export SystemRunner 
\ No newline at end of file
+pub use system::{SystemRunner, MatchMode}; // Export SystemRunner
+pub use sched::SchedRunner; // Export SchedRunner
+pub use gpu::{GpuRunner, GpuConfig, GpuVendor}; // Export GpuRunner
+pub use sse_sink::HttpSseRunner; // Export HttpSseRunner
+pub use remote_sink::{RemoteSink, RemoteSinkConfig, Authenticator, BearerTokenAuthenticator, HmacChallengeAuthenticator}; // Export RemoteSink
+pub use registry::{RunnerRegistry, RunnerConfig, DEFAULT_CONFIG_PATH}; // Export runner registry
+pub use collector::{EventCollector, EventFilter}; // Export predicate-based event collector
+pub use metrics_sink::MetricsSink; // Export Prometheus /metrics HTTP sink
+pub use common::{ClassifiedRunnerError, RunnerErrorKind}; // Export structured runner error classification
\ No newline at end of file
@@ -0,0 +1,297 @@
+use super::common::BinaryExecutor;
+use super::RunnerError;
+use crate::framework::analyzers::{
+    Analyzer, AuthHeaderRemover, CompressionAnalyzer, HTTPFilter, HTTPParser,
+    HTTPTransactionCorrelator, OutputAnalyzer, SSEProcessor, SSLFilter, TimestampNormalizer,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Default location for the runner registry config, relative to the
+/// working directory the collector is started from.
+pub const DEFAULT_CONFIG_PATH: &str = "agentsight.jsonc";
+const SCHEMA_FILE_NAME: &str = "agentsight.schema.json";
+
+/// One externally-defined tracer binary, as declared in `agentsight.jsonc`:
+/// where to find it, how to invoke it, and which analyzer chain to attach
+/// to the events it produces.
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    pub name: String,
+    pub binary_path: String,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    pub analyzers: Vec<String>,
+}
+
+impl RunnerConfig {
+    fn from_value(value: &Value) -> Result<Self, RunnerError> {
+        let name = value.get("name").and_then(|v| v.as_str())
+            .ok_or("Runner entry is missing required string field \"name\"")?
+            .to_string();
+        let binary_path = value.get("binary_path").and_then(|v| v.as_str())
+            .ok_or_else(|| format!("Runner \"{}\" is missing required string field \"binary_path\"", name))?
+            .to_string();
+        let args = value.get("args").and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default();
+        let env = value.get("env").and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let analyzers = value.get("analyzers").and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default();
+
+        Ok(Self { name, binary_path, args, env, analyzers })
+    }
+}
+
+/// Runners declared in `agentsight.jsonc`, built into ready-to-run
+/// `BinaryExecutor`s and analyzer chains by name. Lets operators register
+/// additional eBPF/tracer binaries as a deployment concern rather than a
+/// code change, mirroring the way custom preprocessing programs are
+/// registered declaratively elsewhere in AgentSight.
+pub struct RunnerRegistry {
+    configs: HashMap<String, RunnerConfig>,
+}
+
+impl RunnerRegistry {
+    /// Load `agentsight.jsonc` from `path`. If it doesn't exist, write out a
+    /// default annotated config and its JSON schema alongside it first, so
+    /// an empty registry (no runners configured) is still a valid result.
+    pub fn load_or_init(path: impl AsRef<Path>) -> Result<Self, RunnerError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            Self::write_default_config(path)?;
+        }
+
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let mut stripped = String::new();
+        json_comments::StripComments::new(raw.as_bytes())
+            .read_to_string(&mut stripped)
+            .map_err(|e| format!("Failed to strip comments from {}: {}", path.display(), e))?;
+
+        let document: Value = serde_json::from_str(&stripped)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+        let runners = document.get("runners").and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut configs = HashMap::new();
+        for entry in &runners {
+            let config = RunnerConfig::from_value(entry)?;
+            configs.insert(config.name.clone(), config);
+        }
+
+        Ok(Self { configs })
+    }
+
+    /// Names of every configured runner.
+    pub fn names(&self) -> Vec<&str> {
+        self.configs.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Look up a runner's raw config by name.
+    pub fn get(&self, name: &str) -> Option<&RunnerConfig> {
+        self.configs.get(name)
+    }
+
+    /// Build the `BinaryExecutor` for the named runner, with `${VAR}`
+    /// placeholders in its argument template expanded against the current
+    /// process environment.
+    pub fn build_executor(&self, name: &str) -> Option<BinaryExecutor> {
+        let config = self.configs.get(name)?;
+        let args: Vec<String> = config.args.iter().map(|a| expand_env(a)).collect();
+
+        Some(
+            BinaryExecutor::new(expand_env(&config.binary_path))
+                .with_args(&args)
+                .with_runner_name(config.name.clone())
+                .with_envs(config.env.clone()),
+        )
+    }
+
+    /// Build the analyzer chain declared for the named runner, in order,
+    /// skipping (and logging) any name that doesn't match a known analyzer.
+    pub fn build_analyzer_chain(&self, name: &str) -> Vec<Box<dyn Analyzer>> {
+        match self.configs.get(name) {
+            Some(config) => config.analyzers.iter().filter_map(|id| Self::build_analyzer(name, id)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn build_analyzer(runner_name: &str, id: &str) -> Option<Box<dyn Analyzer>> {
+        let analyzer: Box<dyn Analyzer> = match id {
+            "timestamp_normalizer" => Box::new(TimestampNormalizer::new()),
+            "sse_processor" => Box::new(SSEProcessor::new()),
+            "http_parser" => Box::new(HTTPParser::new()),
+            "http_filter" => Box::new(HTTPFilter::new()),
+            "auth_header_remover" => Box::new(AuthHeaderRemover::new()),
+            "ssl_filter" => Box::new(SSLFilter::new()),
+            "compression" => Box::new(CompressionAnalyzer::new()),
+            "http_transaction_correlator" => Box::new(HTTPTransactionCorrelator::new()),
+            "output" => Box::new(OutputAnalyzer::new()),
+            unknown => {
+                log::warn!(
+                    "Runner \"{}\" declares unknown analyzer \"{}\", skipping it",
+                    runner_name, unknown
+                );
+                return None;
+            }
+        };
+        Some(analyzer)
+    }
+
+    fn write_default_config(path: &Path) -> Result<(), RunnerError> {
+        std::fs::write(path, DEFAULT_CONFIG)
+            .map_err(|e| format!("Failed to write default config to {}: {}", path.display(), e))?;
+
+        let schema_path = path.with_file_name(SCHEMA_FILE_NAME);
+        std::fs::write(&schema_path, SCHEMA)
+            .map_err(|e| format!("Failed to write config schema to {}: {}", schema_path.display(), e))?;
+
+        log::info!(
+            "No runner registry config found at {}, wrote a default one (schema: {})",
+            path.display(),
+            schema_path.display()
+        );
+        Ok(())
+    }
+}
+
+/// Expand `${VAR}` placeholders against the current process environment,
+/// leaving unset variables' placeholders untouched so missing configuration
+/// is visible in the spawned command line rather than silently blanked out.
+fn expand_env(template: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        match rest.find('}') {
+            Some(end) => {
+                let var = &rest[..end];
+                match std::env::var(var) {
+                    Ok(value) => out.push_str(&value),
+                    Err(_) => out.push_str(&format!("${{{}}}", var)),
+                }
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push_str("${");
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+const DEFAULT_CONFIG: &str = r#"{
+  // Declare additional external tracer binaries here without recompiling
+  // AgentSight. Each entry is built into a BinaryExecutor at startup and
+  // looked up by "name". See agentsight.schema.json for the full shape.
+  //
+  // "runners": [
+  //   {
+  //     "name": "my-tracer",
+  //     // May reference an environment variable via ${VAR} placeholders,
+  //     // expanded at startup.
+  //     "binary_path": "/usr/local/bin/my-tracer",
+  //     "args": ["--mode", "${TRACE_MODE}"],
+  //     "env": { "RUST_LOG": "info" },
+  //     // Analyzer chain applied to this runner's events, in order.
+  //     // Known names: timestamp_normalizer, sse_processor, http_parser,
+  //     // http_filter, auth_header_remover, ssl_filter, compression,
+  //     // http_transaction_correlator, output.
+  //     "analyzers": ["timestamp_normalizer", "http_parser", "output"]
+  //   }
+  // ]
+  "runners": []
+}
+"#;
+
+const SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "title": "AgentSight runner registry",
+  "type": "object",
+  "properties": {
+    "runners": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["name", "binary_path"],
+        "properties": {
+          "name": { "type": "string" },
+          "binary_path": { "type": "string" },
+          "args": { "type": "array", "items": { "type": "string" }, "default": [] },
+          "env": { "type": "object", "additionalProperties": { "type": "string" }, "default": {} },
+          "analyzers": { "type": "array", "items": { "type": "string" }, "default": [] }
+        }
+      }
+    }
+  }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn expands_known_and_unknown_placeholders() {
+        std::env::set_var("AGENTSIGHT_TEST_VAR", "value");
+        assert_eq!(expand_env("--flag=${AGENTSIGHT_TEST_VAR}"), "--flag=value");
+        assert_eq!(expand_env("--flag=${AGENTSIGHT_UNSET_VAR}"), "--flag=${AGENTSIGHT_UNSET_VAR}");
+        assert_eq!(expand_env("no placeholders here"), "no placeholders here");
+    }
+
+    #[test]
+    fn writes_default_config_when_missing_and_loads_empty_registry() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(DEFAULT_CONFIG_PATH);
+
+        let registry = RunnerRegistry::load_or_init(&config_path).unwrap();
+        assert!(registry.names().is_empty());
+        assert!(config_path.exists());
+        assert!(dir.path().join(SCHEMA_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn loads_declared_runners_and_builds_executor_and_analyzer_chain() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(DEFAULT_CONFIG_PATH);
+
+        std::fs::write(&config_path, r#"{
+            // comment-tolerant config
+            "runners": [
+                {
+                    "name": "my-tracer",
+                    "binary_path": "/usr/local/bin/my-tracer",
+                    "args": ["--mode", "debug"],
+                    "env": { "RUST_LOG": "info" },
+                    "analyzers": ["timestamp_normalizer", "http_parser", "unknown_analyzer"]
+                }
+            ]
+        }"#).unwrap();
+
+        let registry = RunnerRegistry::load_or_init(&config_path).unwrap();
+        assert_eq!(registry.names(), vec!["my-tracer"]);
+
+        let executor = registry.build_executor("my-tracer");
+        assert!(executor.is_some());
+
+        let chain = registry.build_analyzer_chain("my-tracer");
+        assert_eq!(chain.len(), 2, "unknown_analyzer should be skipped");
+    }
+}
@@ -0,0 +1,84 @@
+use super::common::{AnalyzerProcessor, BinaryExecutor, RunnerProgressTracker};
+use super::{EventStream, Runner, RunnerError, RunnerProgress};
+use crate::framework::analyzers::Analyzer;
+use crate::framework::core::Event;
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use std::sync::Arc;
+
+/// Off-CPU / scheduling-latency runner.
+///
+/// Wraps a `sched_switch`/`sched_wakeup` tracepoint-based tracer binary the
+/// same way [`SslRunner`](super::SslRunner) and
+/// [`ProcessRunner`](super::ProcessRunner) wrap theirs: each tracked thread's
+/// time between leaving and returning to the CPU is measured in the tracer
+/// itself, which emits one JSON event per stall (blocked duration, kernel
+/// wait reason) that this runner turns into an [`Event`].
+pub struct SchedRunner {
+    executor: BinaryExecutor,
+    analyzers: Vec<Box<dyn Analyzer>>,
+    progress: Arc<RunnerProgressTracker>,
+}
+
+impl SchedRunner {
+    /// Create a runner for the `sched` tracer binary at `binary_path`
+    /// (typically `BinaryExtractor::get_sched_path()`).
+    pub fn from_binary_extractor(binary_path: String) -> Self {
+        Self {
+            executor: BinaryExecutor::new(binary_path).with_runner_name("sched".to_string()),
+            analyzers: Vec::new(),
+            progress: Arc::new(RunnerProgressTracker::new("sched")),
+        }
+    }
+
+    /// Add extra command-line arguments, passed straight through to the
+    /// tracer binary (e.g. `-p`/`-c` filters, `--min-latency-us`).
+    pub fn with_args(mut self, args: &[String]) -> Self {
+        self.executor = self.executor.with_args(args);
+        self
+    }
+}
+
+#[async_trait]
+impl Runner for SchedRunner {
+    async fn run(&mut self) -> Result<EventStream, RunnerError> {
+        let json_stream = self.executor.get_json_stream().await?;
+
+        let event_stream: EventStream = Box::pin(json_stream.map(|value| {
+            let timestamp = value.get("timestamp").and_then(|v| v.as_u64()).unwrap_or(0);
+            let pid = value.get("pid").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+            let comm = value.get("comm").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            Event::new_with_timestamp(timestamp, "sched".to_string(), pid, comm, value)
+        }));
+
+        let processed_stream = AnalyzerProcessor::process_through_analyzers(event_stream, &mut self.analyzers).await?;
+
+        self.progress.mark_running();
+        let progress = Arc::clone(&self.progress);
+        let tracked_stream = processed_stream.inspect(move |event| progress.record_event(event));
+
+        Ok(Box::pin(tracked_stream))
+    }
+
+    async fn flush(&mut self) -> Result<(), RunnerError> {
+        self.progress.mark_stopped();
+        AnalyzerProcessor::flush_analyzers(&mut self.analyzers).await
+    }
+
+    fn progress(&self) -> tokio::sync::watch::Receiver<RunnerProgress> {
+        self.progress.receiver()
+    }
+
+    fn add_analyzer(mut self, analyzer: Box<dyn Analyzer>) -> Self {
+        self.analyzers.push(analyzer);
+        self
+    }
+
+    fn name(&self) -> &str {
+        "sched"
+    }
+
+    fn id(&self) -> String {
+        "sched".to_string()
+    }
+}
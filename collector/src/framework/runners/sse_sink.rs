@@ -0,0 +1,306 @@
+use crate::framework::core::Event;
+use super::EventStream;
+use futures::stream::StreamExt;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::time::Duration;
+
+/// Default number of recent events kept around so a reconnecting client
+/// sending `Last-Event-ID` can resume without gaps.
+const DEFAULT_REPLAY_BUFFER: usize = 256;
+
+/// `retry:` hint (in ms) sent to a reconnecting client whose `Last-Event-ID`
+/// has already fallen out of the replay window, telling it how long to wait
+/// before its next reconnect attempt once it's been fast-forwarded to the
+/// live head.
+const EVICTED_RETRY_HINT: Duration = Duration::from_millis(1000);
+
+/// A single rendered SSE frame, cheap to clone and re-send to every subscriber.
+struct SseFrame {
+    id: u64,
+    event_name: String,
+    data: String,
+}
+
+impl SseFrame {
+    fn from_event(id: u64, event: &Event) -> Self {
+        Self {
+            id,
+            // Merged SSE/LLM events (see `SSEProcessor::create_merged_event`)
+            // carry a `dialect` field identifying the provider; prefer that
+            // as the SSE `event:` type so subscribers can tell an Anthropic
+            // response from an OpenAI or Gemini one, falling back to the
+            // event's own `source` for everything else.
+            event_name: event
+                .data
+                .get("dialect")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| event.source.clone()),
+            data: event.to_json().unwrap_or_else(|_| "null".to_string()),
+        }
+    }
+
+    fn render(&self) -> String {
+        format!("id: {}\nevent: {}\ndata: {}\n\n", self.id, self.event_name, self.data)
+    }
+}
+
+/// Sink that serves a merged `EventStream` as Server-Sent Events over HTTP,
+/// so a web dashboard or `curl -N` can subscribe to the live agent output
+/// without a separate exporter process.
+///
+/// Multiple concurrent subscribers are fanned out from the single upstream
+/// stream through a broadcast channel; a bounded ring buffer of recently
+/// sent frames lets reconnecting clients resume via `Last-Event-ID`.
+pub struct HttpSseRunner {
+    addr: SocketAddr,
+    keepalive_interval: Duration,
+    replay_buffer_size: usize,
+}
+
+impl HttpSseRunner {
+    /// Create a new SSE sink bound to the given address (e.g. `127.0.0.1:8081`)
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            keepalive_interval: Duration::from_secs(15),
+            replay_buffer_size: DEFAULT_REPLAY_BUFFER,
+        }
+    }
+
+    /// Override how often idle connections get a `:keepalive` comment line
+    pub fn with_keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
+
+    /// Override how many recent frames are kept for `Last-Event-ID` replay
+    pub fn with_replay_buffer_size(mut self, size: usize) -> Self {
+        self.replay_buffer_size = size;
+        self
+    }
+
+    /// Consume the given merged stream (typically an `AgentRunner`'s output)
+    /// and serve it over HTTP until the stream ends or the server errors.
+    pub async fn serve(self, mut stream: EventStream) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (tx, _rx) = broadcast::channel::<Arc<SseFrame>>(1024);
+        let replay: Arc<Mutex<VecDeque<Arc<SseFrame>>>> =
+            Arc::new(Mutex::new(VecDeque::with_capacity(self.replay_buffer_size)));
+        let replay_size = self.replay_buffer_size;
+
+        let pump_tx = tx.clone();
+        let pump_replay = replay.clone();
+        let pump = tokio::spawn(async move {
+            let mut next_id: u64 = 0;
+            while let Some(event) = stream.next().await {
+                next_id += 1;
+                let frame = Arc::new(SseFrame::from_event(next_id, &event));
+                {
+                    let mut buf = pump_replay.lock().unwrap();
+                    if buf.len() >= replay_size {
+                        buf.pop_front();
+                    }
+                    buf.push_back(frame.clone());
+                }
+                // No subscribers is not an error: frames are simply dropped.
+                let _ = pump_tx.send(frame);
+            }
+        });
+
+        let keepalive_interval = self.keepalive_interval;
+        let make_svc = make_service_fn(move |_conn| {
+            let tx = tx.clone();
+            let replay = replay.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let tx = tx.clone();
+                    let replay = replay.clone();
+                    async move { Ok::<_, Infallible>(handle_request(req, tx, replay, keepalive_interval)) }
+                }))
+            }
+        });
+
+        let server = Server::bind(&self.addr).serve(make_svc);
+        log::info!("HttpSseRunner listening on {}", self.addr);
+
+        tokio::select! {
+            res = server => {
+                if let Err(e) = res {
+                    log::error!("HttpSseRunner server error: {}", e);
+                }
+            }
+            _ = pump => {
+                log::info!("HttpSseRunner upstream event stream ended");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolve what a reconnecting client should receive given the frames still
+/// held in the replay ring buffer. `last_id` is still "within the buffer
+/// window" if the oldest retained frame picks up right where the client
+/// left off (or earlier); if frames between them have already been evicted,
+/// there's a gap that can't be filled, so the client is fast-forwarded to
+/// the live head instead of replayed a range with holes in it. Split out
+/// from [`handle_request`] so this resolution logic can be driven directly
+/// in tests instead of through a real HTTP connection.
+fn resolve_backlog(buf: &VecDeque<Arc<SseFrame>>, last_event_id: Option<u64>) -> (Vec<Arc<SseFrame>>, bool) {
+    match last_event_id {
+        Some(last_id) => match buf.front() {
+            Some(oldest) if last_id + 1 >= oldest.id => (buf.iter().filter(|f| f.id > last_id).cloned().collect(), false),
+            _ => (Vec::new(), true),
+        },
+        None => (Vec::new(), false),
+    }
+}
+
+fn handle_request(
+    req: Request<Body>,
+    tx: broadcast::Sender<Arc<SseFrame>>,
+    replay: Arc<Mutex<VecDeque<Arc<SseFrame>>>>,
+    keepalive_interval: Duration,
+) -> Response<Body> {
+    if req.uri().path() != "/events" {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("not found"))
+            .unwrap();
+    }
+
+    let last_event_id = req
+        .headers()
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok());
+
+    let mut rx = tx.subscribe();
+
+    let (backlog, evicted): (Vec<Arc<SseFrame>>, bool) = {
+        let buf = replay.lock().unwrap();
+        resolve_backlog(&buf, last_event_id)
+    };
+
+    let body_stream = async_stream::stream! {
+        if evicted {
+            yield Ok::<_, Infallible>(hyper::body::Bytes::from(format!("retry: {}\n\n", EVICTED_RETRY_HINT.as_millis())));
+        }
+
+        for frame in backlog {
+            yield Ok::<_, Infallible>(hyper::body::Bytes::from(frame.render()));
+        }
+
+        let mut ticker = tokio::time::interval(keepalive_interval);
+        ticker.tick().await; // first tick fires immediately; skip it
+
+        loop {
+            tokio::select! {
+                msg = rx.recv() => {
+                    match msg {
+                        Ok(frame) => yield Ok(hyper::body::Bytes::from(frame.render())),
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                _ = ticker.tick() => {
+                    yield Ok(hyper::body::Bytes::from(": keepalive\n\n".to_string()));
+                }
+            }
+        }
+    };
+
+    Response::builder()
+        .header("content-type", "text/event-stream")
+        .header("cache-control", "no-cache")
+        .header("connection", "keep-alive")
+        .body(Body::wrap_stream(body_stream))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_frame_render_format() {
+        let event = Event::new("ssl".to_string(), 1234, "ssl".to_string(), serde_json::json!({"k": "v"}));
+        let frame = SseFrame::from_event(7, &event);
+        let rendered = frame.render();
+
+        assert!(rendered.starts_with("id: 7\n"));
+        assert!(rendered.contains("event: ssl\n"));
+        assert!(rendered.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn test_http_sse_runner_defaults() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let runner = HttpSseRunner::new(addr)
+            .with_keepalive_interval(Duration::from_secs(5))
+            .with_replay_buffer_size(10);
+
+        assert_eq!(runner.replay_buffer_size, 10);
+        assert_eq!(runner.keepalive_interval, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_sse_frame_event_name_prefers_dialect_over_source() {
+        let event = Event::new("ssl".to_string(), 1234, "ssl".to_string(), serde_json::json!({"dialect": "anthropic"}));
+        let frame = SseFrame::from_event(1, &event);
+
+        assert!(frame.render().contains("event: anthropic\n"));
+    }
+
+    fn frame(id: u64) -> Arc<SseFrame> {
+        Arc::new(SseFrame {
+            id,
+            event_name: "llm_response".to_string(),
+            data: "{}".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_resolve_backlog_no_last_event_id_replays_nothing() {
+        let buf: VecDeque<Arc<SseFrame>> = vec![frame(1), frame(2)].into();
+        let (backlog, evicted) = resolve_backlog(&buf, None);
+
+        assert!(backlog.is_empty());
+        assert!(!evicted);
+    }
+
+    #[test]
+    fn test_resolve_backlog_within_window_replays_everything_after_last_id() {
+        let buf: VecDeque<Arc<SseFrame>> = vec![frame(1), frame(2), frame(3)].into();
+        let (backlog, evicted) = resolve_backlog(&buf, Some(1));
+
+        assert_eq!(backlog.iter().map(|f| f.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(!evicted);
+    }
+
+    #[test]
+    fn test_resolve_backlog_evicted_id_sends_retry_hint_and_no_backlog() {
+        // Client last saw id 1, but frames 1-4 have since been evicted and
+        // the buffer now starts at 5: there's a gap that can't be replayed.
+        let buf: VecDeque<Arc<SseFrame>> = vec![frame(5), frame(6)].into();
+        let (backlog, evicted) = resolve_backlog(&buf, Some(1));
+
+        assert!(backlog.is_empty());
+        assert!(evicted);
+    }
+
+    #[test]
+    fn test_resolve_backlog_empty_buffer_with_last_id_is_evicted() {
+        let buf: VecDeque<Arc<SseFrame>> = VecDeque::new();
+        let (backlog, evicted) = resolve_backlog(&buf, Some(1));
+
+        assert!(backlog.is_empty());
+        assert!(evicted);
+    }
+}
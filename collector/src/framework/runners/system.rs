@@ -1,30 +1,73 @@
-use super::{Runner, EventStream, RunnerError};
+use super::common::{AnalyzerPipeline, AnalyzerPipelineHandle, RunnerProgressTracker};
+use super::{Runner, EventStream, RunnerError, RunnerProgress, RunnerState};
 use crate::framework::core::Event;
 use crate::framework::analyzers::Analyzer;
 use async_trait::async_trait;
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::pin::Pin;
 use std::time::Duration;
 use tokio::time;
 
+/// How a target process's name filter (`SystemConfig::comm`) is matched
+/// against running processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Match against `/proc/[pid]/comm`, which the kernel truncates to 15
+    /// bytes.
+    Comm,
+    /// Substring match against the full, untruncated `/proc/[pid]/cmdline`.
+    Cmdline,
+    /// Compile the filter as a regex and match it against the full
+    /// `/proc/[pid]/cmdline`.
+    Regex,
+}
+
+/// Selectable families of system-wide metrics beyond the always-collected
+/// CPU/memory pair, so a deployment can skip work it doesn't need (e.g.
+/// per-mount disk enumeration on a host with hundreds of mounts). All three
+/// are on by default, matching this runner's behavior before the families
+/// became selectable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricFamily {
+    /// Per-mount available/total space (`/proc/mounts` + `statvfs`)
+    Disk,
+    /// Aggregate network interface throughput (`/proc/net/dev`)
+    Network,
+    /// One-shot OS distribution/kernel version/logical core count,
+    /// emitted once at the start of the stream rather than every tick
+    Host,
+}
+
 /// Configuration for system resource monitoring
 #[derive(Debug, Clone)]
 pub struct SystemConfig {
     /// Monitoring interval in seconds (default: 10)
     pub interval_secs: u64,
-    /// Monitor specific PID (None = monitor all)
+    /// Monitor specific PID (None = monitor all). Doubles as the
+    /// correlation key against `ProcessRunner`, which emits events for the
+    /// same PID.
     pub pid: Option<u32>,
     /// Process name to monitor (None = monitor all)
     pub comm: Option<String>,
+    /// How `comm` is matched against running processes
+    pub match_mode: MatchMode,
     /// Include child processes in aggregation
     pub include_children: bool,
     /// CPU usage threshold for alerts (%)
     pub cpu_threshold: Option<f64>,
     /// Memory usage threshold for alerts (MB)
     pub memory_threshold: Option<u64>,
+    /// Memory page size in KB, from `sysconf(_SC_PAGESIZE)`
+    pub page_size_kb: u64,
+    /// Clock ticks per second, from `sysconf(_SC_CLK_TCK)`
+    pub clock_ticks_per_sec: u64,
+    /// Collect hwmon thermal sensor readings in system-wide metrics
+    pub enable_thermal: bool,
+    /// Which system-wide metric families (beyond CPU/memory) to collect
+    pub metric_families: Vec<MetricFamily>,
 }
 
 impl Default for SystemConfig {
@@ -33,17 +76,45 @@ impl Default for SystemConfig {
             interval_secs: 10,
             pid: None,
             comm: None,
+            match_mode: MatchMode::Comm,
             include_children: true,
             cpu_threshold: None,
             memory_threshold: None,
+            page_size_kb: sysconf_page_size_kb(),
+            clock_ticks_per_sec: sysconf_clock_ticks_per_sec(),
+            enable_thermal: false,
+            metric_families: vec![MetricFamily::Disk, MetricFamily::Network, MetricFamily::Host],
         }
     }
 }
 
+/// Query the system's memory page size in KB via `sysconf(_SC_PAGESIZE)`.
+/// Falls back to 4 KB (the historical assumption) if the syscall fails.
+fn sysconf_page_size_kb() -> u64 {
+    let bytes = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if bytes > 0 {
+        bytes as u64 / 1024
+    } else {
+        4
+    }
+}
+
+/// Query `CLK_TCK` (clock ticks per second) via `sysconf(_SC_CLK_TCK)`.
+/// Falls back to the common `USER_HZ` default of 100 if the syscall fails.
+fn sysconf_clock_ticks_per_sec() -> u64 {
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as u64
+    } else {
+        100
+    }
+}
+
 /// Runner for collecting system resource metrics (CPU and memory)
 pub struct SystemRunner {
     config: SystemConfig,
-    analyzers: Vec<Box<dyn Analyzer>>,
+    analyzer_pipeline: std::sync::Arc<AnalyzerPipeline>,
+    progress: std::sync::Arc<RunnerProgressTracker>,
 }
 
 impl SystemRunner {
@@ -51,10 +122,18 @@ impl SystemRunner {
     pub fn new() -> Self {
         Self {
             config: SystemConfig::default(),
-            analyzers: Vec::new(),
+            analyzer_pipeline: std::sync::Arc::new(AnalyzerPipeline::new(Vec::new())),
+            progress: std::sync::Arc::new(RunnerProgressTracker::new("system")),
         }
     }
 
+    /// A handle for pushing, removing, or wholesale replacing this runner's
+    /// analyzer chain while it's streaming - e.g. to enable a verbose
+    /// redaction analyzer mid-incident without restarting collection.
+    pub fn analyzer_pipeline(&self) -> AnalyzerPipelineHandle {
+        self.analyzer_pipeline.handle()
+    }
+
     /// Set the monitoring interval in seconds
     pub fn interval(mut self, secs: u64) -> Self {
         self.config.interval_secs = secs;
@@ -73,6 +152,13 @@ impl SystemRunner {
         self
     }
 
+    /// Set how `comm` is matched against running processes (default:
+    /// [`MatchMode::Comm`])
+    pub fn match_mode(mut self, mode: MatchMode) -> Self {
+        self.config.match_mode = mode;
+        self
+    }
+
     /// Include child processes in metrics aggregation
     pub fn include_children(mut self, include: bool) -> Self {
         self.config.include_children = include;
@@ -90,6 +176,20 @@ impl SystemRunner {
         self.config.memory_threshold = Some(threshold);
         self
     }
+
+    /// Enable hwmon thermal sensor readings in system-wide metrics
+    pub fn enable_thermal(mut self, enable: bool) -> Self {
+        self.config.enable_thermal = enable;
+        self
+    }
+
+    /// Select which system-wide metric families (beyond CPU/memory) to
+    /// collect (default: all of [`MetricFamily::Disk`],
+    /// [`MetricFamily::Network`], [`MetricFamily::Host`]).
+    pub fn metric_families(mut self, families: Vec<MetricFamily>) -> Self {
+        self.config.metric_families = families;
+        self
+    }
 }
 
 impl Default for SystemRunner {
@@ -106,18 +206,37 @@ impl Runner for SystemRunner {
         // Create the event stream
         let stream = create_system_event_stream(config);
 
-        // Process through analyzers
-        let event_stream = super::common::AnalyzerProcessor::process_through_analyzers(
-            Box::pin(stream),
-            &mut self.analyzers,
-        )
-        .await?;
+        // Run each event through the current analyzer snapshot; loaded
+        // fresh per event so pushes/removals via `analyzer_pipeline()` take
+        // effect on the next event without restarting the stream.
+        let pipeline = std::sync::Arc::clone(&self.analyzer_pipeline);
+        let event_stream: EventStream = Box::pin(
+            stream
+                .then(move |event| {
+                    let pipeline = std::sync::Arc::clone(&pipeline);
+                    async move { pipeline.process_event(event).await }
+                })
+                .flat_map(futures::stream::iter),
+        );
+
+        self.progress.mark_running();
+        let progress = std::sync::Arc::clone(&self.progress);
+        let tracked_stream = event_stream.inspect(move |event| progress.record_event(event));
+
+        Ok(Box::pin(tracked_stream))
+    }
+
+    async fn flush(&mut self) -> Result<(), RunnerError> {
+        self.progress.mark_stopped();
+        self.analyzer_pipeline.flush().await
+    }
 
-        Ok(event_stream)
+    fn progress(&self) -> tokio::sync::watch::Receiver<RunnerProgress> {
+        self.progress.receiver()
     }
 
-    fn add_analyzer(mut self, analyzer: Box<dyn Analyzer>) -> Self {
-        self.analyzers.push(analyzer);
+    fn add_analyzer(self, analyzer: Box<dyn Analyzer>) -> Self {
+        self.analyzer_pipeline.handle().push_analyzer(analyzer);
         self
     }
 
@@ -150,6 +269,15 @@ fn create_system_event_stream(
     Box::pin(async_stream::stream! {
         let mut interval = time::interval(Duration::from_secs(config.interval_secs));
         let mut previous_stats: HashMap<u32, ProcessStats> = HashMap::new();
+        let mut previous_net: Option<NetSnapshot> = None;
+        let mut previous_pid_net: HashMap<u32, NetSnapshot> = HashMap::new();
+        let mut previous_cpu: Option<CpuStatSnapshot> = None;
+
+        // One-shot host descriptor (OS, kernel, core count), emitted once up
+        // front rather than every tick since it never changes mid-run.
+        if config.metric_families.contains(&MetricFamily::Host) {
+            yield get_host_descriptor_event(get_boot_time_ns());
+        }
 
         loop {
             interval.tick().await;
@@ -165,18 +293,24 @@ fn create_system_event_stream(
                     continue;
                 }
                 // Otherwise, emit system-wide metrics
-                if let Ok(system_metrics) = get_system_wide_metrics(timestamp) {
+                if let Ok(system_metrics) =
+                    get_system_wide_metrics(timestamp, &mut previous_net, &mut previous_cpu, &config)
+                {
                     yield system_metrics;
                 }
                 continue;
             }
 
+            // One /proc scan per tick, shared by every target PID below,
+            // instead of re-walking /proc per recursion level per PID.
+            let tree = ProcessTree::scan();
+
             // Collect metrics for each target PID
             for pid in target_pids {
                 // Get all PIDs to monitor (including children if configured)
                 let pids_to_monitor = if config.include_children {
                     let mut all_pids = vec![pid];
-                    all_pids.extend(get_all_children(pid));
+                    all_pids.extend(tree.descendants(pid));
                     all_pids
                 } else {
                     vec![pid]
@@ -187,7 +321,9 @@ fn create_system_event_stream(
                     pid,
                     &pids_to_monitor,
                     timestamp,
+                    &tree,
                     &mut previous_stats,
+                    &mut previous_pid_net,
                     &config,
                 ) {
                     yield event;
@@ -203,6 +339,31 @@ struct ProcessStats {
     utime: u64,
     stime: u64,
     timestamp: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+    rchar: u64,
+    wchar: u64,
+}
+
+/// Cumulative I/O counters read from `/proc/[pid]/io`
+#[derive(Debug, Clone, Copy, Default)]
+struct ProcessIoStats {
+    read_bytes: u64,
+    write_bytes: u64,
+    rchar: u64,
+    wchar: u64,
+}
+
+/// Cumulative network counters summed across all interfaces except `lo`,
+/// read from a `/proc/net/dev`-shaped file (system-wide or a process's
+/// namespaced `/proc/[pid]/net/dev`).
+#[derive(Debug, Clone, Copy, Default)]
+struct NetSnapshot {
+    rx_bytes: u64,
+    tx_bytes: u64,
+    rx_packets: u64,
+    tx_packets: u64,
+    timestamp: u64,
 }
 
 /// Find PIDs that match the monitoring criteria
@@ -216,7 +377,7 @@ fn find_target_pids(config: &SystemConfig) -> Vec<u32> {
         }
     } else if let Some(ref comm_pattern) = config.comm {
         // Find PIDs by process name
-        find_pids_by_name(comm_pattern)
+        find_pids_by_name(comm_pattern, config.match_mode)
     } else {
         // No specific target - caller should handle system-wide monitoring
         vec![]
@@ -229,19 +390,44 @@ fn process_exists(pid: u32) -> bool {
 }
 
 /// Find all PIDs matching a process name pattern
-fn find_pids_by_name(pattern: &str) -> Vec<u32> {
+fn find_pids_by_name(pattern: &str, mode: MatchMode) -> Vec<u32> {
     let mut matching_pids = Vec::new();
 
+    // Compiled once so MatchMode::Regex doesn't recompile the pattern for
+    // every process in /proc.
+    let compiled_regex = if mode == MatchMode::Regex {
+        regex::Regex::new(pattern).ok()
+    } else {
+        None
+    };
+
     if let Ok(entries) = fs::read_dir("/proc") {
         for entry in entries.flatten() {
-            if let Ok(file_name) = entry.file_name().into_string() {
-                if let Ok(pid) = file_name.parse::<u32>() {
-                    if let Ok(comm) = fs::read_to_string(format!("/proc/{}/comm", pid)) {
-                        if comm.trim().contains(pattern) {
-                            matching_pids.push(pid);
-                        }
-                    }
-                }
+            let Ok(file_name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let Ok(pid) = file_name.parse::<u32>() else {
+                continue;
+            };
+
+            let matches = match mode {
+                MatchMode::Comm => fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .map(|comm| comm.trim().contains(pattern))
+                    .unwrap_or(false),
+                MatchMode::Cmdline => read_process_cmdline(pid)
+                    .map(|cmdline| cmdline.contains(pattern))
+                    .unwrap_or(false),
+                MatchMode::Regex => read_process_cmdline(pid)
+                    .map(|cmdline| {
+                        compiled_regex
+                            .as_ref()
+                            .is_some_and(|re| re.is_match(&cmdline))
+                    })
+                    .unwrap_or(false),
+            };
+
+            if matches {
+                matching_pids.push(pid);
             }
         }
     }
@@ -249,33 +435,103 @@ fn find_pids_by_name(pattern: &str) -> Vec<u32> {
     matching_pids
 }
 
-/// Get all child PIDs recursively
-fn get_all_children(parent_pid: u32) -> Vec<u32> {
-    let mut children = Vec::new();
+/// Read `/proc/[pid]/cmdline` and join its NUL-separated argv into a
+/// space-separated string, for substring/regex matching against the full
+/// (untruncated) command line.
+fn read_process_cmdline(pid: u32) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let raw = fs::read_to_string(format!("/proc/{}/cmdline", pid))?;
+    Ok(raw
+        .split('\u{0}')
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<_>>()
+        .join(" "))
+}
 
-    if let Ok(entries) = fs::read_dir("/proc") {
-        for entry in entries.flatten() {
-            if let Ok(file_name) = entry.file_name().into_string() {
-                if let Ok(pid) = file_name.parse::<u32>() {
-                    if let Ok(stat) = fs::read_to_string(format!("/proc/{}/stat", pid)) {
-                        // Extract PPID from stat file
-                        let fields: Vec<&str> = stat.split_whitespace().collect();
-                        if fields.len() > 3 {
-                            if let Ok(ppid) = fields[3].parse::<u32>() {
-                                if ppid == parent_pid {
-                                    children.push(pid);
-                                    // Recursively get grandchildren
-                                    children.extend(get_all_children(pid));
-                                }
-                            }
-                        }
-                    }
+/// A process's stat fields, parsed once and cached so CPU and start-time
+/// accounting can reuse them instead of re-opening `/proc/[pid]/stat`.
+#[derive(Debug, Clone)]
+struct ProcEntry {
+    ppid: u32,
+    utime: u64,
+    stime: u64,
+    starttime_ticks: u64,
+}
+
+/// A single `/proc` scan: one `/proc/[pid]/stat` read per process, cached
+/// per-PID stat fields, and a PPID -> children adjacency map. Replaces the
+/// old recursive `get_all_children`, which re-read the entire `/proc`
+/// directory (and every stat file) once per recursion level and again for
+/// every target PID each tick, making tree aggregation quadratic in
+/// process count.
+struct ProcessTree {
+    entries: HashMap<u32, ProcEntry>,
+    children: HashMap<u32, Vec<u32>>,
+}
+
+impl ProcessTree {
+    /// Scan `/proc` once, parsing every numeric entry's stat file.
+    fn scan() -> Self {
+        let mut entries = HashMap::new();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+
+        if let Ok(dir_entries) = fs::read_dir("/proc") {
+            for entry in dir_entries.flatten() {
+                let Ok(file_name) = entry.file_name().into_string() else {
+                    continue;
+                };
+                let Ok(pid) = file_name.parse::<u32>() else {
+                    continue;
+                };
+                let Ok(stat) = fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+                    continue;
+                };
+
+                let fields: Vec<&str> = stat.split_whitespace().collect();
+                if fields.len() < 22 {
+                    continue;
                 }
+                let Ok(ppid) = fields[3].parse::<u32>() else {
+                    continue;
+                };
+
+                children.entry(ppid).or_default().push(pid);
+                entries.insert(
+                    pid,
+                    ProcEntry {
+                        ppid,
+                        utime: fields[13].parse().unwrap_or(0),
+                        stime: fields[14].parse().unwrap_or(0),
+                        starttime_ticks: fields[21].parse().unwrap_or(0),
+                    },
+                );
             }
         }
+
+        Self { entries, children }
     }
 
-    children
+    /// Look up a process's cached stat fields.
+    fn get(&self, pid: u32) -> Option<&ProcEntry> {
+        self.entries.get(&pid)
+    }
+
+    /// BFS over the adjacency map for every descendant of `pid`.
+    fn descendants(&self, pid: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut queue: VecDeque<u32> = VecDeque::new();
+        queue.push_back(pid);
+
+        while let Some(current) = queue.pop_front() {
+            if let Some(kids) = self.children.get(&current) {
+                for &child in kids {
+                    result.push(child);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        result
+    }
 }
 
 /// Collect metrics for a process and its children
@@ -283,7 +539,9 @@ fn collect_process_metrics(
     main_pid: u32,
     all_pids: &[u32],
     timestamp: u64,
+    tree: &ProcessTree,
     previous_stats: &mut HashMap<u32, ProcessStats>,
+    previous_pid_net: &mut HashMap<u32, NetSnapshot>,
     config: &SystemConfig,
 ) -> Result<Event, Box<dyn std::error::Error + Send + Sync>> {
     let mut total_rss_kb = 0u64;
@@ -291,6 +549,15 @@ fn collect_process_metrics(
     let mut total_cpu_percent = 0.0f64;
     let mut thread_count = 0u32;
     let mut process_name = String::from("unknown");
+    let mut start_time_secs = 0.0f64;
+    let mut total_read_bps = 0.0f64;
+    let mut total_write_bps = 0.0f64;
+    let mut total_read_bytes = 0u64;
+    let mut total_write_bytes = 0u64;
+    let mut total_rx_bps = 0.0f64;
+    let mut total_tx_bps = 0.0f64;
+    let mut total_rx_bytes = 0u64;
+    let mut total_tx_bytes = 0u64;
 
     // Get main process name
     if let Ok(comm) = fs::read_to_string(format!("/proc/{}/comm", main_pid)) {
@@ -304,28 +571,66 @@ fn collect_process_metrics(
         }
 
         // Get memory info
-        if let Ok((rss, vsz)) = get_process_memory(pid) {
+        if let Ok((rss, vsz)) = get_process_memory(pid, config.page_size_kb) {
             total_rss_kb += rss;
             total_vsz_kb += vsz;
         }
 
-        // Get CPU usage
-        if let Ok(stats) = get_process_cpu_stats(pid) {
+        // Get CPU usage from the cached stat fields (also carries
+        // /proc/[pid]/io counters, if readable)
+        if let Some(entry) = tree.get(pid) {
+            let stats = build_process_stats(pid, entry, timestamp);
+            total_read_bytes += stats.read_bytes;
+            total_write_bytes += stats.write_bytes;
+
+            // Disk rates need the previous snapshot before it's overwritten
+            // by calculate_cpu_percentage below.
+            if let Some((read_bps, write_bps)) = calculate_disk_rates(pid, &stats, previous_stats, timestamp) {
+                total_read_bps += read_bps;
+                total_write_bps += write_bps;
+            }
+
             let cpu_percent = calculate_cpu_percentage(
                 pid,
                 &stats,
                 previous_stats,
                 timestamp,
+                config.clock_ticks_per_sec,
             );
             total_cpu_percent += cpu_percent;
         }
 
-        // Count threads (only for main process)
+        // Namespaced network counters, if the process has its own
+        // `/proc/[pid]/net/dev` (it won't if it shares the host netns and
+        // the file isn't separately listed, but reading it is harmless).
+        if let Ok(net_snapshot) = read_proc_net_dev(&format!("/proc/{}/net/dev", pid), timestamp) {
+            total_rx_bytes += net_snapshot.rx_bytes;
+            total_tx_bytes += net_snapshot.tx_bytes;
+
+            if let Some((rx_bps, tx_bps, _, _)) =
+                calculate_network_rates(&net_snapshot, previous_pid_net.get(&pid))
+            {
+                total_rx_bps += rx_bps;
+                total_tx_bps += tx_bps;
+            }
+            previous_pid_net.insert(pid, net_snapshot);
+        }
+
+        // Count threads and start time (only for main process)
         if pid == main_pid {
             thread_count = get_thread_count(pid);
+            if let Some(entry) = tree.get(pid) {
+                start_time_secs = ticks_to_secs(entry.starttime_ticks, config.clock_ticks_per_sec);
+            }
         }
     }
 
+    // Elapsed running time: uptime-at-start subtracted from current
+    // uptime. Clamp to 0 in case of clock skew or a race where the start
+    // time momentarily exceeds uptime.
+    let uptime_secs = timestamp as f64 / 1_000_000_000.0;
+    let process_uptime_secs = (uptime_secs - start_time_secs).max(0.0);
+
     let children_count = all_pids.len() - 1; // Exclude main process
 
     // Check thresholds for alerts
@@ -357,9 +662,23 @@ fn collect_process_metrics(
             "vsz_kb": total_vsz_kb,
             "vsz_mb": total_vsz_kb / 1024,
         },
+        "disk": {
+            "read_bytes_per_sec": format!("{:.2}", total_read_bps),
+            "write_bytes_per_sec": format!("{:.2}", total_write_bps),
+            "read_bytes_total": total_read_bytes,
+            "write_bytes_total": total_write_bytes,
+        },
+        "network": {
+            "rx_bytes_per_sec": format!("{:.2}", total_rx_bps),
+            "tx_bytes_per_sec": format!("{:.2}", total_tx_bps),
+            "rx_bytes_total": total_rx_bytes,
+            "tx_bytes_total": total_tx_bytes,
+        },
         "process": {
             "threads": thread_count,
             "children": children_count,
+            "start_time_secs": format!("{:.2}", start_time_secs),
+            "uptime_secs": format!("{:.2}", process_uptime_secs),
         },
         "alert": alert,
     });
@@ -374,17 +693,88 @@ fn collect_process_metrics(
 }
 
 /// Get system-wide metrics when no specific process is targeted
-fn get_system_wide_metrics(timestamp: u64) -> Result<Event, Box<dyn std::error::Error + Send + Sync>> {
+fn get_system_wide_metrics(
+    timestamp: u64,
+    previous_net: &mut Option<NetSnapshot>,
+    previous_cpu: &mut Option<CpuStatSnapshot>,
+    config: &SystemConfig,
+) -> Result<Event, Box<dyn std::error::Error + Send + Sync>> {
     // Read system-wide CPU and memory info
     let cpu_cores = num_cpus::get();
 
     // Get load average
     let load_avg = get_load_average()?;
 
+    // Real per-core and aggregate utilization from /proc/stat, since load
+    // averages alone don't reflect actual busy time. The first sample has
+    // no prior snapshot, so utilization is reported as 0 until the second
+    // tick, matching how calculate_cpu_percentage handles its first
+    // measurement.
+    let cpu_snapshot = read_proc_stat_cpu().ok();
+    let (cpu_used_percent, cpu_per_core_percent) = match (&cpu_snapshot, previous_cpu.as_ref()) {
+        (Some(current), Some(prev)) => {
+            let used = calculate_cpu_utilization(&current.aggregate, &prev.aggregate).unwrap_or(0.0);
+            let per_core = current
+                .per_core
+                .iter()
+                .zip(prev.per_core.iter())
+                .map(|(c, p)| calculate_cpu_utilization(c, p).unwrap_or(0.0))
+                .collect::<Vec<_>>();
+            (used, per_core)
+        }
+        _ => (0.0, Vec::new()),
+    };
+    if let Some(snapshot) = cpu_snapshot {
+        *previous_cpu = Some(snapshot);
+    }
+
     // Get total memory info
-    let (total_mem_kb, free_mem_kb, available_mem_kb) = get_system_memory()?;
-    let used_mem_kb = total_mem_kb - available_mem_kb;
-    let used_percent = (used_mem_kb as f64 / total_mem_kb as f64) * 100.0;
+    let memory = get_system_memory()?;
+    let used_mem_kb = memory.total_kb - memory.available_kb;
+    let used_percent = (used_mem_kb as f64 / memory.total_kb as f64) * 100.0;
+    let swap_used_kb = memory.swap_total_kb.saturating_sub(memory.swap_free_kb);
+
+    // Per-mount disk space, opt-out via `MetricFamily::Disk` since scanning
+    // every mount isn't free on a host with hundreds of them.
+    let disk_usage = if config.metric_families.contains(&MetricFamily::Disk) {
+        get_disk_usage()
+    } else {
+        Vec::new()
+    };
+
+    // Network throughput, summed across every interface except loopback,
+    // plus a few UDP/TCP error counters so users can correlate an agent's
+    // syscall activity with real network pressure. Opt-out via
+    // `MetricFamily::Network` the same way disk scanning opts out above.
+    let collect_network = config.metric_families.contains(&MetricFamily::Network);
+    let net_snapshot = if collect_network {
+        read_proc_net_dev("/proc/net/dev", timestamp).ok()
+    } else {
+        None
+    };
+    let net_rates = net_snapshot
+        .as_ref()
+        .and_then(|snap| calculate_network_rates(snap, previous_net.as_ref()));
+    let (rx_bps, tx_bps, rx_pps, tx_pps) = net_rates.unwrap_or((0.0, 0.0, 0.0, 0.0));
+    let snmp = if collect_network {
+        read_proc_net_snmp().unwrap_or_else(|_| json!({}))
+    } else {
+        json!({})
+    };
+    if let Some(snap) = net_snapshot {
+        *previous_net = Some(snap);
+    }
+
+    // Thermal sensors via hwmon, opt-in since most deployments don't need
+    // them and scanning every hwmon chip on every tick isn't free.
+    let thermal_sensors = if config.enable_thermal {
+        read_hwmon_sensors()
+    } else {
+        Vec::new()
+    };
+    let thermal_alert = thermal_sensors
+        .iter()
+        .any(|sensor| sensor.crit_c.is_some_and(|crit| sensor.temp_c >= crit));
 
     let payload = json!({
         "type": "system_wide",
@@ -394,16 +784,46 @@ fn get_system_wide_metrics(timestamp: u64) -> Result<Event, Box<dyn std::error::
             "load_avg_1min": load_avg.0,
             "load_avg_5min": load_avg.1,
             "load_avg_15min": load_avg.2,
+            "used_percent": format!("{:.2}", cpu_used_percent),
+            "per_core": cpu_per_core_percent
+                .iter()
+                .map(|p| format!("{:.2}", p))
+                .collect::<Vec<_>>(),
         },
         "memory": {
-            "total_kb": total_mem_kb,
-            "total_mb": total_mem_kb / 1024,
+            "total_kb": memory.total_kb,
+            "total_mb": memory.total_kb / 1024,
             "used_kb": used_mem_kb,
             "used_mb": used_mem_kb / 1024,
-            "free_kb": free_mem_kb,
-            "available_kb": available_mem_kb,
+            "free_kb": memory.free_kb,
+            "available_kb": memory.available_kb,
             "used_percent": format!("{:.2}", used_percent),
+            "swap_total_kb": memory.swap_total_kb,
+            "swap_free_kb": memory.swap_free_kb,
+            "swap_used_kb": swap_used_kb,
+        },
+        "disk": disk_usage.iter().map(|d| json!({
+            "mount_point": d.mount_point,
+            "total_mb": d.total_kb / 1024,
+            "available_mb": d.available_kb / 1024,
+        })).collect::<Vec<_>>(),
+        "network": {
+            "rx_bytes_per_sec": format!("{:.2}", rx_bps),
+            "tx_bytes_per_sec": format!("{:.2}", tx_bps),
+            "rx_packets_per_sec": format!("{:.2}", rx_pps),
+            "tx_packets_per_sec": format!("{:.2}", tx_pps),
+            "rx_bytes_total": previous_net.map(|s| s.rx_bytes).unwrap_or(0),
+            "tx_bytes_total": previous_net.map(|s| s.tx_bytes).unwrap_or(0),
+            "snmp": snmp,
         },
+        "thermal": thermal_sensors.iter().map(|sensor| json!({
+            "chip": sensor.chip,
+            "label": sensor.label,
+            "temp_c": format!("{:.2}", sensor.temp_c),
+            "max_c": sensor.max_c.map(|v| format!("{:.2}", v)),
+            "crit_c": sensor.crit_c.map(|v| format!("{:.2}", v)),
+        })).collect::<Vec<_>>(),
+        "alert": thermal_alert,
     });
 
     Ok(Event::new_with_timestamp(
@@ -416,7 +836,7 @@ fn get_system_wide_metrics(timestamp: u64) -> Result<Event, Box<dyn std::error::
 }
 
 /// Get process memory usage (RSS and VSZ in KB)
-fn get_process_memory(pid: u32) -> Result<(u64, u64), Box<dyn std::error::Error + Send + Sync>> {
+fn get_process_memory(pid: u32, page_size_kb: u64) -> Result<(u64, u64), Box<dyn std::error::Error + Send + Sync>> {
     let statm = fs::read_to_string(format!("/proc/{}/statm", pid))?;
     let fields: Vec<&str> = statm.split_whitespace().collect();
 
@@ -425,31 +845,186 @@ fn get_process_memory(pid: u32) -> Result<(u64, u64), Box<dyn std::error::Error
     }
 
     // VSZ (virtual size) and RSS (resident set size) in pages
-    let page_size = 4u64; // 4KB page size on most systems
     let vsz_pages: u64 = fields[0].parse()?;
     let rss_pages: u64 = fields[1].parse()?;
 
-    Ok((rss_pages * page_size, vsz_pages * page_size))
+    Ok((rss_pages * page_size_kb, vsz_pages * page_size_kb))
 }
 
-/// Get process CPU statistics from /proc/[pid]/stat
-fn get_process_cpu_stats(pid: u32) -> Result<ProcessStats, Box<dyn std::error::Error + Send + Sync>> {
-    let stat = fs::read_to_string(format!("/proc/{}/stat", pid))?;
-    let fields: Vec<&str> = stat.split_whitespace().collect();
+/// Build a process's CPU/IO stats from an already-parsed `ProcessTree`
+/// entry, only opening `/proc/[pid]/io` (which `stat` doesn't carry).
+fn build_process_stats(pid: u32, entry: &ProcEntry, timestamp: u64) -> ProcessStats {
+    // /proc/[pid]/io requires matching privileges; if it's unreadable
+    // (EACCES) just carry zero counters instead of failing CPU collection.
+    let io = get_process_io(pid).unwrap_or_default();
 
-    if fields.len() < 15 {
-        return Err("Invalid stat format".into());
+    ProcessStats {
+        utime: entry.utime,
+        stime: entry.stime,
+        timestamp,
+        read_bytes: io.read_bytes,
+        write_bytes: io.write_bytes,
+        rchar: io.rchar,
+        wchar: io.wchar,
     }
+}
 
-    let utime: u64 = fields[13].parse()?;
-    let stime: u64 = fields[14].parse()?;
-    let timestamp = get_boot_time_ns();
+/// Convert a cached clock-tick value (e.g. a process's `starttime`) to
+/// seconds, given `CLK_TCK`.
+fn ticks_to_secs(ticks: u64, clock_ticks_per_sec: u64) -> f64 {
+    ticks as f64 / clock_ticks_per_sec as f64
+}
 
-    Ok(ProcessStats {
-        utime,
-        stime,
+/// Read cumulative I/O counters from `/proc/[pid]/io`
+fn get_process_io(pid: u32) -> Result<ProcessIoStats, Box<dyn std::error::Error + Send + Sync>> {
+    let contents = fs::read_to_string(format!("/proc/{}/io", pid))?;
+    let mut stats = ProcessIoStats::default();
+
+    for line in contents.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let value: u64 = value.trim().parse().unwrap_or(0);
+            match key.trim() {
+                "rchar" => stats.rchar = value,
+                "wchar" => stats.wchar = value,
+                "read_bytes" => stats.read_bytes = value,
+                "write_bytes" => stats.write_bytes = value,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Compute per-second read/write byte rates from the delta against the
+/// previous snapshot, using the same `timestamp` basis as CPU accounting.
+/// Returns `None` for the first measurement of a PID (no prior snapshot).
+fn calculate_disk_rates(
+    pid: u32,
+    current: &ProcessStats,
+    previous_stats: &HashMap<u32, ProcessStats>,
+    timestamp: u64,
+) -> Option<(f64, f64)> {
+    let prev = previous_stats.get(&pid)?;
+    let time_delta = (timestamp - prev.timestamp) as f64 / 1_000_000_000.0;
+    if time_delta <= 0.0 {
+        return None;
+    }
+
+    let read_delta = current.read_bytes.saturating_sub(prev.read_bytes);
+    let write_delta = current.write_bytes.saturating_sub(prev.write_bytes);
+
+    Some((read_delta as f64 / time_delta, write_delta as f64 / time_delta))
+}
+
+/// Parse a `/proc/net/dev`-shaped file, summing every interface except
+/// `lo`. Column 1 (after the interface name) is rx_bytes and column 9 is
+/// tx_bytes, with packet/error counts alongside each; this works
+/// unchanged for both the system-wide file and a process's namespaced
+/// `/proc/[pid]/net/dev`.
+fn read_proc_net_dev(
+    path: &str,
+    timestamp: u64,
+) -> Result<NetSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+    let contents = fs::read_to_string(path)?;
+    let mut snapshot = NetSnapshot {
         timestamp,
-    })
+        ..Default::default()
+    };
+
+    // First two lines are headers ("Inter-|   Receive ..." and the field
+    // name row); interface lines follow as "iface: field field ...".
+    for line in contents.lines().skip(2) {
+        let Some((iface, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if iface.trim() == "lo" {
+            continue;
+        }
+
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        if fields.len() < 16 {
+            continue;
+        }
+
+        snapshot.rx_bytes += fields[0].parse().unwrap_or(0);
+        snapshot.rx_packets += fields[1].parse().unwrap_or(0);
+        snapshot.tx_bytes += fields[8].parse().unwrap_or(0);
+        snapshot.tx_packets += fields[9].parse().unwrap_or(0);
+    }
+
+    Ok(snapshot)
+}
+
+/// Parse the `Udp:`/`Tcp:` header+values line pairs of `/proc/net/snmp`,
+/// surfacing a handful of counters useful for correlating syscall activity
+/// with real network pressure.
+fn read_proc_net_snmp() -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+    let contents = fs::read_to_string("/proc/net/snmp")?;
+    let mut udp_in_errors = 0u64;
+    let mut udp_rcvbuf_errors = 0u64;
+    let mut tcp_retrans_segs = 0u64;
+
+    let mut lines = contents.lines();
+    while let Some(header) = lines.next() {
+        let Some(values) = lines.next() else {
+            break;
+        };
+
+        let header_fields: Vec<&str> = header.split_whitespace().collect();
+        let value_fields: Vec<&str> = values.split_whitespace().collect();
+        if header_fields.len() != value_fields.len() {
+            continue;
+        }
+
+        if header.starts_with("Udp:") {
+            for (name, value) in header_fields.iter().zip(value_fields.iter()) {
+                match *name {
+                    "InErrors" => udp_in_errors = value.parse().unwrap_or(0),
+                    "RcvbufErrors" => udp_rcvbuf_errors = value.parse().unwrap_or(0),
+                    _ => {}
+                }
+            }
+        } else if header.starts_with("Tcp:") {
+            for (name, value) in header_fields.iter().zip(value_fields.iter()) {
+                if *name == "RetransSegs" {
+                    tcp_retrans_segs = value.parse().unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    Ok(json!({
+        "udp_in_errors": udp_in_errors,
+        "udp_rcvbuf_errors": udp_rcvbuf_errors,
+        "tcp_retrans_segs": tcp_retrans_segs,
+    }))
+}
+
+/// Compute per-second rx/tx byte and packet rates from the delta against
+/// the previous snapshot. Returns `None` for the first measurement (no
+/// prior snapshot) or a non-positive time delta.
+fn calculate_network_rates(
+    current: &NetSnapshot,
+    previous: Option<&NetSnapshot>,
+) -> Option<(f64, f64, f64, f64)> {
+    let prev = previous?;
+    let time_delta = (current.timestamp - prev.timestamp) as f64 / 1_000_000_000.0;
+    if time_delta <= 0.0 {
+        return None;
+    }
+
+    let rx_bytes_delta = current.rx_bytes.saturating_sub(prev.rx_bytes);
+    let tx_bytes_delta = current.tx_bytes.saturating_sub(prev.tx_bytes);
+    let rx_packets_delta = current.rx_packets.saturating_sub(prev.rx_packets);
+    let tx_packets_delta = current.tx_packets.saturating_sub(prev.tx_packets);
+
+    Some((
+        rx_bytes_delta as f64 / time_delta,
+        tx_bytes_delta as f64 / time_delta,
+        rx_packets_delta as f64 / time_delta,
+        tx_packets_delta as f64 / time_delta,
+    ))
 }
 
 /// Calculate CPU percentage based on previous stats
@@ -458,15 +1033,16 @@ fn calculate_cpu_percentage(
     current: &ProcessStats,
     previous_stats: &mut HashMap<u32, ProcessStats>,
     timestamp: u64,
+    clock_ticks_per_sec: u64,
 ) -> f64 {
     let cpu_percent = if let Some(prev) = previous_stats.get(&pid) {
         let time_delta = (timestamp - prev.timestamp) as f64 / 1_000_000_000.0; // Convert nanoseconds to seconds
         let cpu_delta = (current.utime + current.stime) - (prev.utime + prev.stime);
 
-        // CPU ticks to percentage (assumes USER_HZ = 100)
-        let user_hz = 100.0;
+        // CPU ticks to percentage, using the actual CLK_TCK instead of
+        // assuming USER_HZ == 100 (wrong on e.g. some ARM kernels).
         if time_delta > 0.0 {
-            (cpu_delta as f64 / user_hz / time_delta) * 100.0
+            (cpu_delta as f64 / clock_ticks_per_sec as f64 / time_delta) * 100.0
         } else {
             0.0
         }
@@ -487,6 +1063,171 @@ fn get_thread_count(pid: u32) -> u32 {
         .unwrap_or(1)
 }
 
+/// Cumulative jiffie counters from one `cpu`/`cpuN` line of `/proc/stat`.
+#[derive(Debug, Clone, Copy, Default)]
+struct CpuJiffies {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl CpuJiffies {
+    fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    fn idle_total(&self) -> u64 {
+        self.idle + self.iowait
+    }
+}
+
+/// A snapshot of `/proc/stat`'s aggregate `cpu` line plus each `cpuN` line.
+#[derive(Debug, Clone, Default)]
+struct CpuStatSnapshot {
+    aggregate: CpuJiffies,
+    per_core: Vec<CpuJiffies>,
+}
+
+/// Parse the `cpu`/`cpuN` lines of `/proc/stat`, which are always the
+/// first lines in the file.
+fn read_proc_stat_cpu() -> Result<CpuStatSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+    let contents = fs::read_to_string("/proc/stat")?;
+    let mut snapshot = CpuStatSnapshot::default();
+
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let Some(&label) = fields.first() else {
+            continue;
+        };
+        if !label.starts_with("cpu") {
+            break;
+        }
+        if fields.len() < 9 {
+            continue;
+        }
+
+        let jiffies = CpuJiffies {
+            user: fields[1].parse().unwrap_or(0),
+            nice: fields[2].parse().unwrap_or(0),
+            system: fields[3].parse().unwrap_or(0),
+            idle: fields[4].parse().unwrap_or(0),
+            iowait: fields[5].parse().unwrap_or(0),
+            irq: fields[6].parse().unwrap_or(0),
+            softirq: fields[7].parse().unwrap_or(0),
+            steal: fields[8].parse().unwrap_or(0),
+        };
+
+        if label == "cpu" {
+            snapshot.aggregate = jiffies;
+        } else {
+            snapshot.per_core.push(jiffies);
+        }
+    }
+
+    Ok(snapshot)
+}
+
+/// Percentage busy = `busy_delta / total_delta * 100`, where `total` sums
+/// every jiffie field and `busy` excludes idle and iowait. Returns `None`
+/// for a non-positive total delta (including the first sample, where
+/// there's no prior snapshot to diff against).
+fn calculate_cpu_utilization(current: &CpuJiffies, previous: &CpuJiffies) -> Option<f64> {
+    let total_delta = current.total().saturating_sub(previous.total());
+    if total_delta == 0 {
+        return None;
+    }
+
+    let idle_delta = current
+        .idle_total()
+        .saturating_sub(previous.idle_total());
+    let busy_delta = total_delta.saturating_sub(idle_delta);
+
+    Some(busy_delta as f64 / total_delta as f64 * 100.0)
+}
+
+/// A single hwmon temperature sensor reading.
+#[derive(Debug, Clone)]
+struct ThermalSensor {
+    chip: String,
+    label: Option<String>,
+    temp_c: f64,
+    max_c: Option<f64>,
+    crit_c: Option<f64>,
+}
+
+/// Scan `/sys/class/hwmon/hwmon*` for temperature sensors. Each hwmon
+/// directory names its chip in `name` and exposes one or more
+/// `tempN_input` files (millidegrees Celsius) alongside optional
+/// `tempN_label`/`tempN_max`/`tempN_crit` siblings.
+fn read_hwmon_sensors() -> Vec<ThermalSensor> {
+    let mut sensors = Vec::new();
+
+    let Ok(hwmon_dirs) = fs::read_dir("/sys/class/hwmon") else {
+        return sensors;
+    };
+
+    for hwmon_dir in hwmon_dirs.flatten() {
+        let hwmon_path = hwmon_dir.path();
+        let chip = fs::read_to_string(hwmon_path.join("name"))
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|_| "unknown".to_string());
+
+        let Ok(sensor_files) = fs::read_dir(&hwmon_path) else {
+            continue;
+        };
+
+        for sensor_file in sensor_files.flatten() {
+            let Ok(file_name) = sensor_file.file_name().into_string() else {
+                continue;
+            };
+            let Some(index) = file_name
+                .strip_prefix("temp")
+                .and_then(|rest| rest.strip_suffix("_input"))
+            else {
+                continue;
+            };
+
+            let Some(millidegrees) = fs::read_to_string(sensor_file.path())
+                .ok()
+                .and_then(|value| value.trim().parse::<f64>().ok())
+            else {
+                continue;
+            };
+
+            let read_milli_c = |suffix: &str| {
+                fs::read_to_string(hwmon_path.join(format!("temp{}{}", index, suffix)))
+                    .ok()
+                    .and_then(|value| value.trim().parse::<f64>().ok())
+                    .map(|v| v / 1000.0)
+            };
+
+            sensors.push(ThermalSensor {
+                chip: chip.clone(),
+                label: fs::read_to_string(hwmon_path.join(format!("temp{}_label", index)))
+                    .ok()
+                    .map(|label| label.trim().to_string()),
+                temp_c: millidegrees / 1000.0,
+                max_c: read_milli_c("_max"),
+                crit_c: read_milli_c("_crit"),
+            });
+        }
+    }
+
+    sensors
+}
+
 /// Get system load average
 fn get_load_average() -> Result<(f64, f64, f64), Box<dyn std::error::Error + Send + Sync>> {
     let loadavg = fs::read_to_string("/proc/loadavg")?;
@@ -503,24 +1244,128 @@ fn get_load_average() -> Result<(f64, f64, f64), Box<dyn std::error::Error + Sen
     ))
 }
 
+/// System-wide RAM and swap, in KB, as read from `/proc/meminfo`.
+#[derive(Debug, Clone, Copy, Default)]
+struct MemoryInfo {
+    total_kb: u64,
+    free_kb: u64,
+    available_kb: u64,
+    swap_total_kb: u64,
+    swap_free_kb: u64,
+}
+
 /// Get system memory information from /proc/meminfo
-fn get_system_memory() -> Result<(u64, u64, u64), Box<dyn std::error::Error + Send + Sync>> {
+fn get_system_memory() -> Result<MemoryInfo, Box<dyn std::error::Error + Send + Sync>> {
     let meminfo = fs::read_to_string("/proc/meminfo")?;
-    let mut total_kb = 0u64;
-    let mut free_kb = 0u64;
-    let mut available_kb = 0u64;
+    let mut info = MemoryInfo::default();
 
     for line in meminfo.lines() {
         if line.starts_with("MemTotal:") {
-            total_kb = parse_meminfo_line(line)?;
+            info.total_kb = parse_meminfo_line(line)?;
         } else if line.starts_with("MemFree:") {
-            free_kb = parse_meminfo_line(line)?;
+            info.free_kb = parse_meminfo_line(line)?;
         } else if line.starts_with("MemAvailable:") {
-            available_kb = parse_meminfo_line(line)?;
+            info.available_kb = parse_meminfo_line(line)?;
+        } else if line.starts_with("SwapTotal:") {
+            info.swap_total_kb = parse_meminfo_line(line)?;
+        } else if line.starts_with("SwapFree:") {
+            info.swap_free_kb = parse_meminfo_line(line)?;
         }
     }
 
-    Ok((total_kb, free_kb, available_kb))
+    Ok(info)
+}
+
+/// Available/total space for one mounted filesystem, from `statvfs`.
+#[derive(Debug, Clone)]
+struct DiskUsage {
+    mount_point: String,
+    total_kb: u64,
+    available_kb: u64,
+}
+
+/// Mount-point prefixes for pseudo filesystems that don't represent real
+/// disk capacity and would just add noise to a per-mount listing.
+const PSEUDO_FS_TYPES: &[&str] = &[
+    "proc", "sysfs", "devtmpfs", "devpts", "tmpfs", "cgroup", "cgroup2", "overlay", "squashfs",
+    "mqueue", "debugfs", "tracefs", "pstore", "bpf", "securityfs", "autofs", "binfmt_misc",
+];
+
+/// Enumerate real (non-pseudo) mounted filesystems from `/proc/mounts` and
+/// `statvfs(2)` each one for available/total space.
+fn get_disk_usage() -> Vec<DiskUsage> {
+    let Ok(mounts) = fs::read_to_string("/proc/mounts") else {
+        return Vec::new();
+    };
+
+    let mut usages = Vec::new();
+    for line in mounts.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        let mount_point = fields[1];
+        let fs_type = fields[2];
+        if PSEUDO_FS_TYPES.contains(&fs_type) {
+            continue;
+        }
+
+        if let Some((total_kb, available_kb)) = statvfs_kb(mount_point) {
+            usages.push(DiskUsage {
+                mount_point: mount_point.to_string(),
+                total_kb,
+                available_kb,
+            });
+        }
+    }
+
+    usages
+}
+
+/// Call `statvfs(2)` on `path`, returning (total_kb, available_kb).
+fn statvfs_kb(path: &str) -> Option<(u64, u64)> {
+    let c_path = std::ffi::CString::new(path).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+    if rc != 0 {
+        return None;
+    }
+
+    let block_size_kb = stat.f_frsize as u64 / 1024;
+    Some((
+        stat.f_blocks as u64 * block_size_kb,
+        stat.f_bavail as u64 * block_size_kb,
+    ))
+}
+
+/// One-shot host descriptor: OS distribution, kernel version, and logical
+/// core count. Emitted once at stream start (gated on
+/// [`MetricFamily::Host`]) rather than every tick, since none of it changes
+/// for the life of the process.
+fn get_host_descriptor_event(timestamp: u64) -> Event {
+    let os_pretty_name = fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|contents| {
+            contents.lines().find_map(|line| {
+                line.strip_prefix("PRETTY_NAME=")
+                    .map(|v| v.trim_matches('"').to_string())
+            })
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let kernel_version = fs::read_to_string("/proc/version")
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    let payload = json!({
+        "type": "host_descriptor",
+        "timestamp": timestamp,
+        "os_pretty_name": os_pretty_name,
+        "kernel_version": kernel_version,
+        "logical_cores": num_cpus::get(),
+    });
+
+    Event::new_with_timestamp(timestamp, "system".to_string(), 0, "system".to_string(), payload)
 }
 
 /// Parse a single line from /proc/meminfo
@@ -544,6 +1389,236 @@ mod tests {
         assert_eq!(runner.config.interval_secs, 10);
     }
 
+    #[test]
+    fn test_sysconf_page_size_and_clock_ticks_are_queried() {
+        // Both sysconf calls should succeed on any Linux host and produce
+        // sane, non-zero values rather than the old hardcoded assumptions.
+        let config = SystemConfig::default();
+        assert!(config.page_size_kb > 0);
+        assert!(config.clock_ticks_per_sec > 0);
+    }
+
+    #[test]
+    fn test_get_process_io_reads_current_process() {
+        let pid = std::process::id();
+        let stats = get_process_io(pid);
+        // /proc/self/io is always readable by the owning process
+        assert!(stats.is_ok());
+    }
+
+    #[test]
+    fn test_calculate_disk_rates_none_on_first_sample() {
+        let previous_stats: HashMap<u32, ProcessStats> = HashMap::new();
+        let current = ProcessStats {
+            utime: 0,
+            stime: 0,
+            timestamp: 1_000_000_000,
+            read_bytes: 100,
+            write_bytes: 50,
+            rchar: 0,
+            wchar: 0,
+        };
+
+        assert!(calculate_disk_rates(1234, &current, &previous_stats, 1_000_000_000).is_none());
+    }
+
+    #[test]
+    fn test_calculate_disk_rates_computes_bytes_per_sec() {
+        let mut previous_stats: HashMap<u32, ProcessStats> = HashMap::new();
+        previous_stats.insert(1234, ProcessStats {
+            utime: 0,
+            stime: 0,
+            timestamp: 0,
+            read_bytes: 1000,
+            write_bytes: 500,
+            rchar: 0,
+            wchar: 0,
+        });
+
+        let current = ProcessStats {
+            utime: 0,
+            stime: 0,
+            timestamp: 2_000_000_000, // 2 seconds later
+            read_bytes: 3000,
+            write_bytes: 1500,
+            rchar: 0,
+            wchar: 0,
+        };
+
+        let (read_bps, write_bps) = calculate_disk_rates(1234, &current, &previous_stats, 2_000_000_000).unwrap();
+        assert_eq!(read_bps, 1000.0); // 2000 bytes / 2 secs
+        assert_eq!(write_bps, 500.0); // 1000 bytes / 2 secs
+    }
+
+    #[test]
+    fn test_process_tree_scan_finds_current_process() {
+        let pid = std::process::id();
+        let config = SystemConfig::default();
+        let tree = ProcessTree::scan();
+
+        let entry = tree.get(pid).expect("current process should be in the scan");
+        assert!(ticks_to_secs(entry.starttime_ticks, config.clock_ticks_per_sec) >= 0.0);
+    }
+
+    #[test]
+    fn test_process_tree_descendants_follows_adjacency_map() {
+        let mut tree = ProcessTree {
+            entries: HashMap::new(),
+            children: HashMap::new(),
+        };
+        tree.children.insert(1, vec![2, 3]);
+        tree.children.insert(2, vec![4]);
+
+        let mut descendants = tree.descendants(1);
+        descendants.sort();
+        assert_eq!(descendants, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_read_hwmon_sensors_reports_sane_temperatures() {
+        // Hosts without /sys/class/hwmon (e.g. some containers) should
+        // just yield no sensors rather than erroring; hosts with hwmon
+        // chips should report plausible Celsius readings.
+        for sensor in read_hwmon_sensors() {
+            assert!(sensor.temp_c > -100.0 && sensor.temp_c < 200.0);
+        }
+    }
+
+    #[test]
+    fn test_system_runner_enable_thermal_defaults_to_false() {
+        let runner = SystemRunner::new();
+        assert!(!runner.config.enable_thermal);
+
+        let runner = runner.enable_thermal(true);
+        assert!(runner.config.enable_thermal);
+    }
+
+    #[test]
+    fn test_read_proc_stat_cpu_reads_host_cores() {
+        let snapshot = read_proc_stat_cpu().unwrap();
+        assert!(snapshot.aggregate.total() > 0);
+        assert_eq!(snapshot.per_core.len(), num_cpus::get());
+    }
+
+    #[test]
+    fn test_calculate_cpu_utilization_none_on_zero_delta() {
+        let jiffies = CpuJiffies {
+            user: 100,
+            idle: 200,
+            ..Default::default()
+        };
+        assert!(calculate_cpu_utilization(&jiffies, &jiffies).is_none());
+    }
+
+    #[test]
+    fn test_calculate_cpu_utilization_computes_busy_percentage() {
+        let previous = CpuJiffies {
+            user: 100,
+            idle: 900,
+            ..Default::default()
+        };
+        let current = CpuJiffies {
+            user: 150, // +50 busy
+            idle: 950, // +50 idle
+            ..Default::default()
+        };
+
+        // total_delta = 100, busy_delta = 50 -> 50%
+        assert_eq!(calculate_cpu_utilization(&current, &previous), Some(50.0));
+    }
+
+    #[test]
+    fn test_read_proc_net_dev_reads_host_interfaces() {
+        // /proc/net/dev is always readable; this is a smoke test that the
+        // parser doesn't choke on whatever interfaces the host has.
+        let snapshot = read_proc_net_dev("/proc/net/dev", 1_000_000_000);
+        assert!(snapshot.is_ok());
+    }
+
+    #[test]
+    fn test_calculate_network_rates_none_on_first_sample() {
+        let current = NetSnapshot {
+            rx_bytes: 100,
+            tx_bytes: 50,
+            rx_packets: 10,
+            tx_packets: 5,
+            timestamp: 1_000_000_000,
+        };
+
+        assert!(calculate_network_rates(&current, None).is_none());
+    }
+
+    #[test]
+    fn test_calculate_network_rates_computes_bytes_per_sec() {
+        let previous = NetSnapshot {
+            rx_bytes: 1000,
+            tx_bytes: 500,
+            rx_packets: 100,
+            tx_packets: 50,
+            timestamp: 0,
+        };
+        let current = NetSnapshot {
+            rx_bytes: 3000,
+            tx_bytes: 1500,
+            rx_packets: 300,
+            tx_packets: 150,
+            timestamp: 2_000_000_000, // 2 seconds later
+        };
+
+        let (rx_bps, tx_bps, rx_pps, tx_pps) =
+            calculate_network_rates(&current, Some(&previous)).unwrap();
+        assert_eq!(rx_bps, 1000.0); // 2000 bytes / 2 secs
+        assert_eq!(tx_bps, 500.0); // 1000 bytes / 2 secs
+        assert_eq!(rx_pps, 100.0); // 200 packets / 2 secs
+        assert_eq!(tx_pps, 50.0); // 100 packets / 2 secs
+    }
+
+    #[test]
+    fn test_read_process_cmdline_joins_nul_separated_argv() {
+        let pid = std::process::id();
+        let cmdline = read_process_cmdline(pid);
+        // /proc/self/cmdline is always readable by the owning process, and
+        // shouldn't contain any literal NUL bytes once joined.
+        assert!(cmdline.is_ok());
+        assert!(!cmdline.unwrap().contains('\u{0}'));
+    }
+
+    #[test]
+    fn test_find_pids_by_name_cmdline_matches_full_command_line() {
+        let pid = std::process::id();
+        let cmdline = read_process_cmdline(pid).unwrap();
+        // Any non-empty slice of our own cmdline should match in Cmdline
+        // mode even if it's longer than the 15-byte comm truncation.
+        let Some(needle) = cmdline.split_whitespace().next() else {
+            return;
+        };
+
+        let matches = find_pids_by_name(needle, MatchMode::Cmdline);
+        assert!(matches.contains(&pid));
+    }
+
+    #[test]
+    fn test_find_pids_by_name_regex_matches_full_command_line() {
+        let pid = std::process::id();
+        let cmdline = read_process_cmdline(pid).unwrap();
+        let Some(needle) = cmdline.split_whitespace().next() else {
+            return;
+        };
+        let pattern = regex::escape(needle);
+
+        let matches = find_pids_by_name(&pattern, MatchMode::Regex);
+        assert!(matches.contains(&pid));
+    }
+
+    #[test]
+    fn test_system_runner_match_mode_defaults_to_comm() {
+        let runner = SystemRunner::new();
+        assert_eq!(runner.config.match_mode, MatchMode::Comm);
+
+        let runner = runner.match_mode(MatchMode::Cmdline);
+        assert_eq!(runner.config.match_mode, MatchMode::Cmdline);
+    }
+
     #[test]
     fn test_system_runner_with_config() {
         let runner = SystemRunner::new()
@@ -603,4 +1678,143 @@ mod tests {
             Err(e) => panic!("Failed to run SystemRunner: {}", e),
         }
     }
+
+    #[tokio::test]
+    async fn test_system_runner_progress_reaches_running_and_tallies_events() {
+        use futures::StreamExt;
+        use tokio::time::{timeout, Duration};
+
+        let current_pid = std::process::id();
+        let mut runner = SystemRunner::new().interval(1).pid(current_pid);
+
+        let mut progress = runner.progress();
+        assert_eq!(progress.borrow().state, RunnerState::Starting);
+
+        let mut stream = runner.run().await.unwrap();
+        progress.changed().await.unwrap();
+        assert_eq!(progress.borrow_and_update().state, RunnerState::Running);
+
+        timeout(Duration::from_secs(3), stream.next()).await
+            .expect("should collect at least one event before the timeout")
+            .expect("stream should not end");
+        progress.changed().await.unwrap();
+        assert!(progress.borrow().events_emitted >= 1);
+    }
+
+    #[test]
+    fn test_system_config_default_metric_families_includes_everything() {
+        let config = SystemConfig::default();
+        assert!(config.metric_families.contains(&MetricFamily::Disk));
+        assert!(config.metric_families.contains(&MetricFamily::Network));
+        assert!(config.metric_families.contains(&MetricFamily::Host));
+    }
+
+    #[test]
+    fn test_system_runner_metric_families_builder_overrides_default() {
+        let runner = SystemRunner::new().metric_families(vec![MetricFamily::Host]);
+        assert_eq!(runner.config.metric_families, vec![MetricFamily::Host]);
+    }
+
+    #[test]
+    fn test_get_system_memory_reads_total_and_swap() {
+        // /proc/meminfo always has MemTotal; swap may legitimately be zero
+        // on a swapless host, so only total is asserted non-zero.
+        let memory = get_system_memory().unwrap();
+        assert!(memory.total_kb > 0);
+        assert!(memory.available_kb <= memory.total_kb);
+    }
+
+    #[test]
+    fn test_get_disk_usage_includes_root_mount() {
+        let usages = get_disk_usage();
+        assert!(usages.iter().any(|d| d.mount_point == "/"));
+        for disk in &usages {
+            assert!(disk.total_kb > 0);
+        }
+    }
+
+    #[test]
+    fn test_statvfs_kb_reports_sane_root_capacity() {
+        let (total_kb, available_kb) = statvfs_kb("/").expect("/ should always be statvfs-able");
+        assert!(total_kb > 0);
+        assert!(available_kb <= total_kb);
+    }
+
+    #[test]
+    fn test_get_host_descriptor_event_payload_shape() {
+        let event = get_host_descriptor_event(1_000_000_000);
+        assert_eq!(event.source, "system");
+        assert_eq!(event.data.get("type").and_then(|v| v.as_str()), Some("host_descriptor"));
+        assert!(event.data.get("logical_cores").and_then(|v| v.as_u64()).unwrap_or(0) > 0);
+        assert!(event.data.get("kernel_version").is_some());
+    }
+
+    /// Tags every event's payload with `tag_field: tag_value`, so tests can
+    /// tell which analyzers an event actually passed through.
+    struct TaggingAnalyzer {
+        tag_field: &'static str,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::framework::analyzers::Analyzer for TaggingAnalyzer {
+        async fn process(&mut self, stream: EventStream) -> Result<EventStream, crate::framework::analyzers::AnalyzerError> {
+            let field = self.tag_field;
+            Ok(Box::pin(stream.map(move |mut event| {
+                if let Some(obj) = event.data.as_object_mut() {
+                    obj.insert(field.to_string(), serde_json::Value::Bool(true));
+                }
+                event
+            })))
+        }
+
+        fn name(&self) -> &str {
+            "tagging_analyzer"
+        }
+    }
+
+    fn make_test_event() -> Event {
+        Event::new_with_timestamp(0, "system".to_string(), 0, "test".to_string(), json!({}))
+    }
+
+    #[tokio::test]
+    async fn test_analyzer_pipeline_handle_push_takes_effect_without_reconstruction() {
+        let pipeline = AnalyzerPipeline::new(Vec::new());
+        let handle = pipeline.handle();
+
+        let before = pipeline.process_event(make_test_event()).await;
+        assert!(before[0].data.get("tagged").is_none());
+
+        handle.push_analyzer(Box::new(TaggingAnalyzer { tag_field: "tagged" }));
+
+        let after = pipeline.process_event(make_test_event()).await;
+        assert_eq!(after[0].data.get("tagged").and_then(|v| v.as_bool()), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_analyzer_pipeline_handle_remove_drops_an_analyzer_by_id() {
+        let pipeline = AnalyzerPipeline::new(Vec::new());
+        let handle = pipeline.handle();
+
+        let id = handle.push_analyzer(Box::new(TaggingAnalyzer { tag_field: "tagged" }));
+        let tagged = pipeline.process_event(make_test_event()).await;
+        assert!(tagged[0].data.get("tagged").is_some());
+
+        handle.remove_analyzer(id);
+        let untagged = pipeline.process_event(make_test_event()).await;
+        assert!(untagged[0].data.get("tagged").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_analyzer_pipeline_handle_replace_swaps_the_whole_chain() {
+        let pipeline = AnalyzerPipeline::new(vec![
+            Box::new(TaggingAnalyzer { tag_field: "old" }) as Box<dyn crate::framework::analyzers::Analyzer>,
+        ]);
+        let handle = pipeline.handle();
+
+        handle.replace_analyzers(vec![Box::new(TaggingAnalyzer { tag_field: "new" })]);
+
+        let event = pipeline.process_event(make_test_event()).await;
+        assert!(event[0].data.get("old").is_none());
+        assert_eq!(event[0].data.get("new").and_then(|v| v.as_bool()), Some(true));
+    }
 }
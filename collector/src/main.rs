@@ -1,6 +1,6 @@
 use clap::{Parser, Subcommand};
 use futures::stream::StreamExt;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tokio::signal;
 use tokio::sync::broadcast;
 
@@ -9,37 +9,141 @@ mod server;
 
 use framework::{
     binary_extractor::BinaryExtractor,
-    runners::{SslRunner, ProcessRunner, AgentRunner, SystemRunner, RunnerError, Runner},
-    analyzers::{OutputAnalyzer, FileLogger, SSEProcessor, HTTPParser, HTTPFilter, AuthHeaderRemover, SSLFilter, TimestampNormalizer, print_global_http_filter_metrics, print_global_ssl_filter_metrics}
+    runners::{SslRunner, ProcessRunner, AgentRunner, SystemRunner, SchedRunner, RunnerError, Runner},
+    analyzers::{OutputAnalyzer, FileLogger, SSEProcessor, HTTPParser, HTTPFilter, AuthHeaderRemover, SSLFilter, TimestampNormalizer, MetricsCollector, ForwardAnalyzer, ForwardConfig, ForwardFormat, print_global_ssl_filter_metrics, print_global_prometheus_metrics, print_global_forward_metrics, Analyzer, run_replay as run_replay_chain, print_replay_report}
 };
 
 use server::WebServer;
 
 static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
 
+/// Count of events that were never delivered to any broadcast subscriber,
+/// either because the channel was lagged past or because every receiver had
+/// already been dropped. Surfaced alongside the analyzer metrics on SIGINT.
+static DROPPED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Events allowed to sit unconsumed in the broadcast channel above a
+/// runner's configured buffer before `forward_event` pauses the caller to
+/// let subscribers catch up. Kept small relative to the buffer so producers
+/// resume well before the channel is actually full.
+const LOW_WATERMARK_SLACK: usize = 10;
+
+/// Send `event` on `event_sender`, applying high/low watermark backpressure
+/// against `buffer_capacity`: once the channel's queued depth reaches
+/// capacity, this pauses (without busy-spinning) until it drains back below
+/// `buffer_capacity - LOW_WATERMARK_SLACK`, then sends. Failed sends (no
+/// receivers, or a lagged subscriber) are counted in `DROPPED_EVENTS` rather
+/// than silently ignored.
+async fn forward_event(
+    event_sender: &broadcast::Sender<framework::core::Event>,
+    buffer_capacity: usize,
+    event: framework::core::Event,
+) {
+    let low_watermark = buffer_capacity.saturating_sub(LOW_WATERMARK_SLACK);
+    while event_sender.len() >= buffer_capacity {
+        tokio::time::sleep(tokio::time::Duration::from_millis(1)).await;
+        if event_sender.len() < low_watermark {
+            break;
+        }
+    }
+
+    if event_sender.send(event).is_err() {
+        DROPPED_EVENTS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
 fn convert_runner_error(e: RunnerError) -> Box<dyn std::error::Error> {
     Box::new(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
 }
 
+/// Install a background SIGINT watcher that prints the same summary metrics
+/// the process used to print right before calling `process::exit(0)`.
+///
+/// It no longer exits the process itself: each live command's consume loop
+/// (see [`consume_with_shutdown`]) independently watches for Ctrl+C/SIGTERM
+/// so it can stop pulling from its runner, flush analyzers, and give the web
+/// server a chance to wind down before `main` returns on its own. This
+/// handler just sets [`SHUTDOWN_REQUESTED`] and prints metrics that aren't
+/// tied to any particular runner.
 async fn setup_signal_handler() {
     let mut sigint = signal::unix::signal(signal::unix::SignalKind::interrupt())
         .expect("Failed to install SIGINT handler");
-    
+
     tokio::spawn(async move {
         sigint.recv().await;
         println!("\n\nReceived SIGINT, shutting down...");
-        
-        // Print HTTP filter metrics using the global function
-        print_global_http_filter_metrics();
-        
+
         // Print SSL filter metrics using the global function
         print_global_ssl_filter_metrics();
-        
+
+        // Print the Prometheus-style counters/gauges collected by
+        // MetricsCollector, including HTTPFilter's per-analyzer/per-expression
+        // counters (served live on MetricsSink's `/metrics` endpoint too).
+        print_global_prometheus_metrics();
+
+        // Print forwarding metrics using the global function
+        print_global_forward_metrics();
+
+        println!(
+            "[Event Broadcast Metrics] dropped_events={}",
+            DROPPED_EVENTS.load(Ordering::Relaxed)
+        );
+
         SHUTDOWN_REQUESTED.store(true, Ordering::Relaxed);
-        std::process::exit(0);
     });
 }
 
+/// Drive `stream` to completion, forwarding events to the web server if
+/// enabled, stopping early on Ctrl+C or SIGTERM. Once the loop ends (either
+/// way), `runner`'s analyzer chain is flushed so nothing buffered (e.g. a
+/// rotating [`FileLogger`] segment) is lost, and `server_handle` - if a web
+/// server was started - is given a bounded grace period to finish before
+/// this returns, instead of being silently dropped mid-request.
+async fn consume_with_shutdown(
+    runner: &mut dyn Runner,
+    mut stream: framework::runners::EventStream,
+    event_sender: &broadcast::Sender<framework::core::Event>,
+    enable_server: bool,
+    max_events_buffer: usize,
+    server_handle: Option<tokio::task::JoinHandle<()>>,
+) {
+    let mut sigterm = signal::unix::signal(signal::unix::SignalKind::terminate())
+        .expect("Failed to install SIGTERM handler");
+
+    loop {
+        tokio::select! {
+            event = stream.next() => {
+                match event {
+                    Some(event) => {
+                        if enable_server {
+                            forward_event(event_sender, max_events_buffer, event).await;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            _ = signal::ctrl_c() => {
+                println!("\nReceived Ctrl+C, stopping event stream...");
+                break;
+            }
+            _ = sigterm.recv() => {
+                println!("\nReceived SIGTERM, stopping event stream...");
+                break;
+            }
+        }
+    }
+
+    if let Err(e) = runner.flush().await {
+        eprintln!("Warning: failed to flush analyzers cleanly: {}", e);
+    }
+
+    if let Some(handle) = server_handle {
+        if tokio::time::timeout(std::time::Duration::from_secs(5), handle).await.is_err() {
+            eprintln!("Warning: web server did not shut down within the grace period");
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -66,6 +170,12 @@ enum Commands {
         /// Disable authorization header removal from HTTP traffic
         #[arg(long)]
         disable_auth_removal: bool,
+        /// Reject a reassembled HTTP request/status line longer than this many bytes
+        #[arg(long, default_value = "8192")]
+        http_max_request_line: usize,
+        /// Reject a reassembled HTTP header block larger than this many bytes
+        #[arg(long, default_value = "65536")]
+        http_max_header_bytes: usize,
         /// SSL filter patterns to exclude events (can be used multiple times)
         #[arg(long)]
         ssl_filter: Vec<String>,
@@ -78,6 +188,9 @@ enum Commands {
         /// Maximum log file size in MB (used with --rotate-logs)
         #[arg(long, default_value = "10")]
         max_log_size: u64,
+        /// Maximum number of events buffered for broadcast subscribers before producers pause
+        #[arg(long, default_value = "1000")]
+        max_events_buffer: usize,
         /// Start web server on port 7395
         #[arg(long)]
         server: bool,
@@ -105,6 +218,9 @@ enum Commands {
         /// Maximum log file size in MB (used with --rotate-logs)
         #[arg(long, default_value = "10")]
         max_log_size: u64,
+        /// Maximum number of events buffered for broadcast subscribers before producers pause
+        #[arg(long, default_value = "1000")]
+        max_events_buffer: usize,
         /// Start web server on port 7395
         #[arg(long)]
         server: bool,
@@ -118,6 +234,42 @@ enum Commands {
         #[arg(last = true)]
         args: Vec<String>,
     },
+    /// Explain agent stalls via off-CPU / scheduling-latency tracing
+    Sched {
+        /// Process command name to monitor
+        #[arg(short = 'c', long)]
+        comm: Option<String>,
+        /// Process PID to monitor
+        #[arg(short = 'p', long)]
+        pid: Option<u32>,
+        /// Minimum off-CPU duration to report, in microseconds
+        #[arg(long, default_value = "1000")]
+        min_latency_us: u64,
+        /// Output file
+        #[arg(short = 'o', long, default_value = "sched.log")]
+        output: String,
+        /// Suppress console output
+        #[arg(short, long)]
+        quiet: bool,
+        /// Enable log rotation
+        #[arg(long)]
+        rotate_logs: bool,
+        /// Maximum log file size in MB (used with --rotate-logs)
+        #[arg(long, default_value = "10")]
+        max_log_size: u64,
+        /// Maximum number of events buffered for broadcast subscribers before producers pause
+        #[arg(long, default_value = "1000")]
+        max_events_buffer: usize,
+        /// Start web server on port 7395
+        #[arg(long)]
+        server: bool,
+        /// Server port (used with --server)
+        #[arg(long, default_value = "7395")]
+        server_port: u16,
+        /// Log file to serve via API (used with --server)
+        #[arg(long)]
+        log_file: Option<String>,
+    },
     /// Combined SSL and Process monitoring with configurable options
     Trace {
         /// Enable SSL monitoring
@@ -162,15 +314,34 @@ enum Commands {
         #[arg(long, default_value = "2")]
         system_interval: u64,
 
+        /// Enable off-CPU/scheduling-latency monitoring
+        #[arg(long)]
+        sched: bool,
+        /// Minimum off-CPU duration to report, in microseconds
+        #[arg(long, default_value = "1000")]
+        sched_min_latency_us: u64,
+
         /// HTTP filters (applied to SSL runner after HTTP parsing)
         #[arg(long)]
         http_filter: Vec<String>,
         /// Disable authorization header removal from HTTP traffic
         #[arg(long)]
         disable_auth_removal: bool,
+        /// Reject a reassembled HTTP request/status line longer than this many bytes
+        #[arg(long, default_value = "8192")]
+        http_max_request_line: usize,
+        /// Reject a reassembled HTTP header block larger than this many bytes
+        #[arg(long, default_value = "65536")]
+        http_max_header_bytes: usize,
         /// Path to the binary executable to monitor (e.g., ~/.nvm/versions/node/v20.0.0/bin/node)
         #[arg(long)]
         binary_path: Option<String>,
+        /// Forward every event to an external OTLP/Vector-style HTTP collector (e.g. http://localhost:4318/v1/logs)
+        #[arg(long)]
+        forward_url: Option<String>,
+        /// Wire format for --forward-url: "ndjson" (default) or "otlp"
+        #[arg(long, default_value = "ndjson")]
+        forward_format: String,
         /// Output file
         #[arg(short = 'o', long, default_value = "trace.log")]
         output: Option<String>,
@@ -183,6 +354,9 @@ enum Commands {
         /// Maximum log file size in MB (used with --rotate-logs)
         #[arg(long, default_value = "10")]
         max_log_size: u64,
+        /// Maximum number of events buffered for broadcast subscribers before producers pause
+        #[arg(long, default_value = "1000")]
+        max_events_buffer: usize,
         /// Start web server on port 7395
         #[arg(long)]
         server: bool,
@@ -211,6 +385,9 @@ enum Commands {
         /// Maximum log file size in MB (used with --rotate-logs)
         #[arg(long, default_value = "10")]
         max_log_size: u64,
+        /// Maximum number of events buffered for broadcast subscribers before producers pause
+        #[arg(long, default_value = "1000")]
+        max_events_buffer: usize,
         /// Server port (used with --server, always enabled)
         #[arg(long, default_value = "7395")]
         server_port: u16,
@@ -250,6 +427,9 @@ enum Commands {
         /// Maximum log file size in MB (used with --rotate-logs)
         #[arg(long, default_value = "10")]
         max_log_size: u64,
+        /// Maximum number of events buffered for broadcast subscribers before producers pause
+        #[arg(long, default_value = "1000")]
+        max_events_buffer: usize,
         /// Start web server on port 7395
         #[arg(long)]
         server: bool,
@@ -260,6 +440,26 @@ enum Commands {
         #[arg(long)]
         log_file: Option<String>,
     },
+    /// Benchmark the analyzer pipeline against a previously recorded log
+    Replay {
+        /// Recorded log file to replay (as written by --output/FileLogger)
+        log_file: String,
+        /// Enable SSE processing in the replayed chain
+        #[arg(long)]
+        sse_merge: bool,
+        /// Enable HTTP parsing in the replayed chain (implies --sse-merge)
+        #[arg(long)]
+        http_parser: bool,
+        /// HTTP filter patterns to exclude events (can be used multiple times)
+        #[arg(long)]
+        http_filter: Vec<String>,
+        /// Disable authorization header removal from HTTP traffic
+        #[arg(long)]
+        disable_auth_removal: bool,
+        /// SSL filter patterns to exclude events (can be used multiple times)
+        #[arg(long)]
+        ssl_filter: Vec<String>,
+    },
 }
 
 #[tokio::main]
@@ -278,10 +478,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let binary_extractor = BinaryExtractor::new().await?;
     
     match &cli.command {
-        Commands::Ssl { sse_merge, http_parser, http_raw_data, http_filter, disable_auth_removal, ssl_filter, quiet, rotate_logs, max_log_size, server, server_port, log_file, binary_path, args } => run_raw_ssl(&binary_extractor, *sse_merge, *http_parser, *http_raw_data, http_filter, *disable_auth_removal, ssl_filter, *quiet, *rotate_logs, *max_log_size, *server, *server_port, log_file.as_deref(), binary_path.as_deref(), args).await.map_err(convert_runner_error)?,
-        Commands::Process { quiet, rotate_logs, max_log_size, server, server_port, log_file, args } => run_raw_process(&binary_extractor, *quiet, *rotate_logs, *max_log_size, *server, *server_port, log_file.as_deref(), args).await.map_err(convert_runner_error)?,
-        Commands::Trace { ssl, ssl_uid, pid, comm, ssl_filter, ssl_handshake, ssl_http, ssl_raw_data, process, duration, mode, system, system_interval, http_filter, disable_auth_removal, binary_path, output, quiet, rotate_logs, max_log_size, server, server_port, log_file } => run_trace(&binary_extractor, *ssl, *pid, *ssl_uid, comm.as_deref(), ssl_filter, *ssl_handshake, *ssl_http, *ssl_raw_data, *process, *duration, *mode, *system, *system_interval, http_filter, *disable_auth_removal, binary_path.as_deref(), output.as_deref(), *quiet, *rotate_logs, *max_log_size, *server, *server_port, log_file.as_deref()).await.map_err(convert_runner_error)?,
-        Commands::Record { comm, binary_path, output, rotate_logs, max_log_size, server_port, log_file } => {
+        Commands::Ssl { sse_merge, http_parser, http_raw_data, http_filter, disable_auth_removal, http_max_request_line, http_max_header_bytes, ssl_filter, quiet, rotate_logs, max_log_size, max_events_buffer, server, server_port, log_file, binary_path, args } => run_raw_ssl(&binary_extractor, *sse_merge, *http_parser, *http_raw_data, http_filter, *disable_auth_removal, *http_max_request_line, *http_max_header_bytes, ssl_filter, *quiet, *rotate_logs, *max_log_size, *max_events_buffer, *server, *server_port, log_file.as_deref(), binary_path.as_deref(), args).await.map_err(convert_runner_error)?,
+        Commands::Process { quiet, rotate_logs, max_log_size, max_events_buffer, server, server_port, log_file, args } => run_raw_process(&binary_extractor, *quiet, *rotate_logs, *max_log_size, *max_events_buffer, *server, *server_port, log_file.as_deref(), args).await.map_err(convert_runner_error)?,
+        Commands::Sched { comm, pid, min_latency_us, output, quiet, rotate_logs, max_log_size, max_events_buffer, server, server_port, log_file } => run_sched(&binary_extractor, comm.as_deref(), *pid, *min_latency_us, output, *quiet, *rotate_logs, *max_log_size, *max_events_buffer, *server, *server_port, log_file.as_deref()).await.map_err(convert_runner_error)?,
+        Commands::Trace { ssl, ssl_uid, pid, comm, ssl_filter, ssl_handshake, ssl_http, ssl_raw_data, process, duration, mode, system, system_interval, sched, sched_min_latency_us, http_filter, disable_auth_removal, http_max_request_line, http_max_header_bytes, binary_path, forward_url, forward_format, output, quiet, rotate_logs, max_log_size, max_events_buffer, server, server_port, log_file } => run_trace(&binary_extractor, *ssl, *pid, *ssl_uid, comm.as_deref(), ssl_filter, *ssl_handshake, *ssl_http, *ssl_raw_data, *process, *duration, *mode, *system, *system_interval, *sched, *sched_min_latency_us, http_filter, *disable_auth_removal, *http_max_request_line, *http_max_header_bytes, binary_path.as_deref(), forward_url.as_deref(), forward_format, output.as_deref(), *quiet, *rotate_logs, *max_log_size, *max_events_buffer, *server, *server_port, log_file.as_deref()).await.map_err(convert_runner_error)?,
+        Commands::Record { comm, binary_path, output, rotate_logs, max_log_size, max_events_buffer, server_port, log_file } => {
             // Predefined filter patterns optimized for agent monitoring
             let http_filter_patterns = vec![
                 "request.path_prefix=/v1/rgstr | response.status_code=202 | request.method=HEAD | response.body=".to_string(),
@@ -291,9 +492,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             ];
 
             // Enable system monitoring by default for record command
-            run_trace(&binary_extractor, true, None, None, Some(comm), &ssl_filter_patterns, false, true, false, true, None, None, true, 2, &http_filter_patterns, false, binary_path.as_deref(), Some(output), true, *rotate_logs, *max_log_size, true, *server_port, log_file.as_deref().or(Some(output))).await.map_err(convert_runner_error)?
+            run_trace(&binary_extractor, true, None, None, Some(comm), &ssl_filter_patterns, false, true, false, true, None, None, true, 2, false, 1000, &http_filter_patterns, false, 8192, 65536, binary_path.as_deref(), None, "ndjson", Some(output), true, *rotate_logs, *max_log_size, *max_events_buffer, true, *server_port, log_file.as_deref().or(Some(output))).await.map_err(convert_runner_error)?
         },
-        Commands::System { interval, pid, comm, no_children, cpu_threshold, memory_threshold, output, quiet, rotate_logs, max_log_size, server, server_port, log_file } => run_system(*interval, *pid, comm.as_deref(), !*no_children, *cpu_threshold, *memory_threshold, output, *quiet, *rotate_logs, *max_log_size, *server, *server_port, log_file.as_deref()).await.map_err(convert_runner_error)?,
+        Commands::System { interval, pid, comm, no_children, cpu_threshold, memory_threshold, output, quiet, rotate_logs, max_log_size, max_events_buffer, server, server_port, log_file } => run_system(*interval, *pid, comm.as_deref(), !*no_children, *cpu_threshold, *memory_threshold, output, *quiet, *rotate_logs, *max_log_size, *max_events_buffer, *server, *server_port, log_file.as_deref()).await.map_err(convert_runner_error)?,
+        Commands::Replay { log_file, sse_merge, http_parser, http_filter, disable_auth_removal, ssl_filter } => run_replay(log_file, *sse_merge, *http_parser, http_filter, *disable_auth_removal, ssl_filter).await?,
     }
     
     Ok(())
@@ -301,14 +503,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 
 /// Show raw SSL events as JSON with optional chunk merging and HTTP parsing
-async fn run_raw_ssl(binary_extractor: &BinaryExtractor, enable_chunk_merger: bool, enable_http_parser: bool, include_raw_data: bool, http_filter_patterns: &Vec<String>, disable_auth_removal: bool, ssl_filter_patterns: &Vec<String>, quiet: bool, rotate_logs: bool, max_log_size: u64, enable_server: bool, server_port: u16, log_file: Option<&str>, binary_path: Option<&str>, args: &Vec<String>) -> Result<(), RunnerError> {
+async fn run_raw_ssl(binary_extractor: &BinaryExtractor, enable_chunk_merger: bool, enable_http_parser: bool, include_raw_data: bool, http_filter_patterns: &Vec<String>, disable_auth_removal: bool, http_max_request_line: usize, http_max_header_bytes: usize, ssl_filter_patterns: &Vec<String>, quiet: bool, rotate_logs: bool, max_log_size: u64, max_events_buffer: usize, enable_server: bool, server_port: u16, log_file: Option<&str>, binary_path: Option<&str>, args: &Vec<String>) -> Result<(), RunnerError> {
     println!("Raw SSL Events");
     println!("{}", "=".repeat(60));
-    
+
     let mut ssl_runner = SslRunner::from_binary_extractor(binary_extractor.get_sslsniff_path());
 
     // Set up event broadcasting for server if enabled
-    let (event_sender, _event_receiver) = broadcast::channel(1000);
+    let (event_sender, _event_receiver) = broadcast::channel(max_events_buffer);
 
     // Build arguments list with binary_path if provided
     let mut final_args = Vec::new();
@@ -328,7 +530,7 @@ async fn run_raw_ssl(binary_extractor: &BinaryExtractor, enable_chunk_merger: bo
 
     // Add SSL filter if patterns are provided
     if !ssl_filter_patterns.is_empty() {
-        ssl_runner = ssl_runner.add_analyzer(Box::new(SSLFilter::with_patterns(ssl_filter_patterns.clone())));
+        ssl_runner = ssl_runner.add_analyzer(Box::new(SSLFilter::with_patterns(ssl_filter_patterns.clone())?));
     }
     
     // Add analyzers based on flags - when HTTP parser is enabled, always enable SSE merge first
@@ -336,16 +538,19 @@ async fn run_raw_ssl(binary_extractor: &BinaryExtractor, enable_chunk_merger: bo
         ssl_runner = ssl_runner.add_analyzer(Box::new(SSEProcessor::new_with_timeout(30000)));
         
         // Create HTTP parser with appropriate configuration
-        let http_parser = if include_raw_data {
+        let mut http_parser = if include_raw_data {
             HTTPParser::new()
         } else {
             HTTPParser::new().disable_raw_data()
         };
+        http_parser = http_parser
+            .with_max_request_line(http_max_request_line)
+            .with_max_header_bytes(http_max_header_bytes);
         ssl_runner = ssl_runner.add_analyzer(Box::new(http_parser));
-        
+
         // Add HTTP filter if patterns are provided
         if !http_filter_patterns.is_empty() {
-            ssl_runner = ssl_runner.add_analyzer(Box::new(HTTPFilter::with_patterns(http_filter_patterns.clone())));
+            ssl_runner = ssl_runner.add_analyzer(Box::new(HTTPFilter::with_patterns(http_filter_patterns.clone())?));
         }
         
         // Add authorization header remover by default (unless disabled)
@@ -373,38 +578,33 @@ async fn run_raw_ssl(binary_extractor: &BinaryExtractor, enable_chunk_merger: bo
             } else {
                 FileLogger::new("ssl.log").unwrap()
             }
-        ));
-    
+        ))
+        .add_analyzer(Box::new(MetricsCollector::new()));
+
     if !quiet {
         ssl_runner = ssl_runner.add_analyzer(Box::new(OutputAnalyzer::new()));
     }
-    
+
     // Start web server if enabled
-    let _server_handle = start_web_server_if_enabled(enable_server, server_port, log_file.or(Some("ssl.log")), event_sender.clone()).await
+    let server_handle = start_web_server_if_enabled(enable_server, server_port, log_file.or(Some("ssl.log")), event_sender.clone()).await
         .map_err(|e| RunnerError::from(format!("Failed to start server: {}", e)))?;
-    
-    let mut stream = ssl_runner.run().await?;
-    
-    // Consume the stream to actually process events
-    while let Some(event) = stream.next().await {
-        // Forward events to web server if enabled
-        if enable_server {
-            let _ = event_sender.send(event);
-        }
-    }
-    
+
+    let stream = ssl_runner.run().await?;
+
+    consume_with_shutdown(&mut ssl_runner, stream, &event_sender, enable_server, max_events_buffer, server_handle).await;
+
     Ok(())
 }
 
 /// Show raw process events as JSON
-async fn run_raw_process(binary_extractor: &BinaryExtractor, quiet: bool, rotate_logs: bool, max_log_size: u64, enable_server: bool, server_port: u16, log_file: Option<&str>, args: &Vec<String>) -> Result<(), RunnerError> {
+async fn run_raw_process(binary_extractor: &BinaryExtractor, quiet: bool, rotate_logs: bool, max_log_size: u64, max_events_buffer: usize, enable_server: bool, server_port: u16, log_file: Option<&str>, args: &Vec<String>) -> Result<(), RunnerError> {
     println!("Raw Process Events");
     println!("{}", "=".repeat(60));
-    
+
     let mut process_runner = ProcessRunner::from_binary_extractor(binary_extractor.get_process_path());
 
     // Set up event broadcasting for server if enabled
-    let (event_sender, _event_receiver) = broadcast::channel(1000);
+    let (event_sender, _event_receiver) = broadcast::channel(max_events_buffer);
 
     // Add additional arguments if provided
     if !args.is_empty() {
@@ -412,7 +612,9 @@ async fn run_raw_process(binary_extractor: &BinaryExtractor, quiet: bool, rotate
     }
 
     // Add TimestampNormalizer first to convert nanoseconds since boot to milliseconds since epoch
-    process_runner = process_runner.add_analyzer(Box::new(TimestampNormalizer::new()));
+    process_runner = process_runner
+        .add_analyzer(Box::new(TimestampNormalizer::new()))
+        .add_analyzer(Box::new(MetricsCollector::new()));
 
     if !quiet {
         process_runner = process_runner.add_analyzer(Box::new(OutputAnalyzer::new()));
@@ -428,19 +630,13 @@ async fn run_raw_process(binary_extractor: &BinaryExtractor, quiet: bool, rotate
         ));
     
     // Start web server if enabled
-    let _server_handle = start_web_server_if_enabled(enable_server, server_port, log_file.or(Some("ssl.log")), event_sender.clone()).await
+    let server_handle = start_web_server_if_enabled(enable_server, server_port, log_file.or(Some("ssl.log")), event_sender.clone()).await
         .map_err(|e| RunnerError::from(format!("Failed to start server: {}", e)))?;
-    
+
     println!("Starting process event stream with raw JSON output (press Ctrl+C to stop):");
-    let mut stream = process_runner.run().await?;
+    let stream = process_runner.run().await?;
 
-    // Consume the stream to actually process events
-    while let Some(event) = stream.next().await {
-        // Forward events to web server if enabled
-        if enable_server {
-            let _ = event_sender.send(event);
-        }
-    }
+    consume_with_shutdown(&mut process_runner, stream, &event_sender, enable_server, max_events_buffer, server_handle).await;
 
     Ok(())
 }
@@ -461,22 +657,29 @@ async fn run_trace(
     mode: Option<u32>,
     system_enabled: bool,
     system_interval: u64,
+    sched_enabled: bool,
+    sched_min_latency_us: u64,
     http_filter: &[String],
     disable_auth_removal: bool,
+    http_max_request_line: usize,
+    http_max_header_bytes: usize,
     binary_path: Option<&str>,
+    forward_url: Option<&str>,
+    forward_format: &str,
     output: Option<&str>,
     quiet: bool,
     rotate_logs: bool,
     max_log_size: u64,
+    max_events_buffer: usize,
     enable_server: bool,
     server_port: u16,
     log_file: Option<&str>,
 ) -> Result<(), RunnerError> {
     println!("Trace Monitoring");
     println!("{}", "=".repeat(60));
-    
+
     // Set up event broadcasting for server if enabled
-    let (event_sender, _event_receiver) = broadcast::channel(1000);
+    let (event_sender, _event_receiver) = broadcast::channel(max_events_buffer);
     
     let mut agent = AgentRunner::new("trace");
     
@@ -510,22 +713,25 @@ async fn run_trace(
 
         // Add SSL-specific analyzers
         if !ssl_filter.is_empty() {
-            ssl_runner = ssl_runner.add_analyzer(Box::new(SSLFilter::with_patterns(ssl_filter.to_vec())));
+            ssl_runner = ssl_runner.add_analyzer(Box::new(SSLFilter::with_patterns(ssl_filter.to_vec())?));
         }
         
         if ssl_http {
             ssl_runner = ssl_runner.add_analyzer(Box::new(SSEProcessor::new_with_timeout(30000)));
             
-            let http_parser = if ssl_raw_data {
+            let mut http_parser = if ssl_raw_data {
                 HTTPParser::new()
             } else {
                 HTTPParser::new().disable_raw_data()
             };
+            http_parser = http_parser
+                .with_max_request_line(http_max_request_line)
+                .with_max_header_bytes(http_max_header_bytes);
             ssl_runner = ssl_runner.add_analyzer(Box::new(http_parser));
-            
+
             // Add HTTP filter to SSL runner if patterns are provided
             if !http_filter.is_empty() {
-                ssl_runner = ssl_runner.add_analyzer(Box::new(HTTPFilter::with_patterns(http_filter.to_vec())));
+                ssl_runner = ssl_runner.add_analyzer(Box::new(HTTPFilter::with_patterns(http_filter.to_vec())?));
             }
             
             // Add authorization header remover by default (unless disabled)
@@ -591,13 +797,45 @@ async fn run_trace(
         println!("‚úì System monitoring enabled (interval: {}s)", system_interval);
     }
 
+    // Add off-CPU/scheduling-latency runner if enabled
+    if sched_enabled {
+        let mut sched_runner = SchedRunner::from_binary_extractor(binary_extractor.get_sched_path());
+
+        // Configure sched runner arguments (sched supports -c, -p, --min-latency-us)
+        let mut sched_args = Vec::new();
+        if let Some(comm_filter) = comm {
+            sched_args.extend(["-c".to_string(), comm_filter.to_string()]);
+        }
+        if let Some(pid_filter) = pid {
+            sched_args.extend(["-p".to_string(), pid_filter.to_string()]);
+        }
+        sched_args.extend(["--min-latency-us".to_string(), sched_min_latency_us.to_string()]);
+        sched_runner = sched_runner.with_args(&sched_args);
+
+        // Add TimestampNormalizer first
+        sched_runner = sched_runner.add_analyzer(Box::new(TimestampNormalizer::new()));
+
+        agent = agent.add_runner(Box::new(sched_runner));
+        println!("‚úì Off-CPU/scheduling-latency monitoring enabled (min latency: {}us)", sched_min_latency_us);
+    }
+
     // Ensure at least one runner is enabled
-    if !ssl_enabled && !process_enabled && !system_enabled {
-        return Err("At least one monitoring type must be enabled (--ssl, --process, or --system)".into());
+    if !ssl_enabled && !process_enabled && !system_enabled && !sched_enabled {
+        return Err("At least one monitoring type must be enabled (--ssl, --process, --system, or --sched)".into());
     }
     
     // Add global analyzers (HTTP filter is now added to SSL runner instead)
-    
+    agent = agent.add_global_analyzer(Box::new(MetricsCollector::new()));
+
+    if let Some(url) = forward_url {
+        agent = agent.add_global_analyzer(Box::new(ForwardAnalyzer::new(ForwardConfig {
+            url: url.to_string(),
+            format: ForwardFormat::parse(forward_format),
+            ..Default::default()
+        })));
+        println!("‚úì Forwarding events to {}", url);
+    }
+
     if let Some(output_path) = output {
         agent = agent.add_global_analyzer(Box::new(
             if rotate_logs {
@@ -620,19 +858,13 @@ async fn run_trace(
     println!("Press Ctrl+C to stop");
     
     // Start web server if enabled
-    let _server_handle = start_web_server_if_enabled(enable_server, server_port, log_file.or(Some("ssl.log")), event_sender.clone()).await
+    let server_handle = start_web_server_if_enabled(enable_server, server_port, log_file.or(Some("ssl.log")), event_sender.clone()).await
         .map_err(|e| RunnerError::from(format!("Failed to start server: {}", e)))?;
-    
-    let mut stream = agent.run().await?;
-    
-    // Consume the stream to actually process events
-    while let Some(event) = stream.next().await {
-        // Forward events to web server if enabled
-        if enable_server {
-            let _ = event_sender.send(event);
-        }
-    }
-    
+
+    let stream = agent.run().await?;
+
+    consume_with_shutdown(&mut agent, stream, &event_sender, enable_server, max_events_buffer, server_handle).await;
+
     Ok(())
 }
 
@@ -650,6 +882,7 @@ async fn run_system(
     quiet: bool,
     rotate_logs: bool,
     max_log_size: u64,
+    max_events_buffer: usize,
     enable_server: bool,
     server_port: u16,
     log_file: Option<&str>,
@@ -690,10 +923,12 @@ async fn run_system(
     println!("Starting system monitoring (press Ctrl+C to stop):");
 
     // Set up event broadcasting for server if enabled
-    let (event_sender, _event_receiver) = broadcast::channel(1000);
+    let (event_sender, _event_receiver) = broadcast::channel(max_events_buffer);
 
     // Add TimestampNormalizer first
-    system_runner = system_runner.add_analyzer(Box::new(TimestampNormalizer::new()));
+    system_runner = system_runner
+        .add_analyzer(Box::new(TimestampNormalizer::new()))
+        .add_analyzer(Box::new(MetricsCollector::new()));
 
     // Add file logger
     system_runner = system_runner
@@ -711,24 +946,140 @@ async fn run_system(
     }
 
     // Start web server if enabled
-    let _server_handle = start_web_server_if_enabled(
+    let server_handle = start_web_server_if_enabled(
+        enable_server,
+        server_port,
+        log_file.or(Some(output)),
+        event_sender.clone(),
+    ).await
+        .map_err(|e| RunnerError::from(format!("Failed to start server: {}", e)))?;
+
+    let stream = system_runner.run().await?;
+
+    consume_with_shutdown(&mut system_runner, stream, &event_sender, enable_server, max_events_buffer, server_handle).await;
+
+    Ok(())
+}
+
+/// Off-CPU/scheduling-latency monitoring, standing alone the way `run_system`
+/// does (as opposed to being composed into `run_trace`'s `AgentRunner`).
+async fn run_sched(
+    binary_extractor: &BinaryExtractor,
+    comm: Option<&str>,
+    pid: Option<u32>,
+    min_latency_us: u64,
+    output: &str,
+    quiet: bool,
+    rotate_logs: bool,
+    max_log_size: u64,
+    max_events_buffer: usize,
+    enable_server: bool,
+    server_port: u16,
+    log_file: Option<&str>,
+) -> Result<(), RunnerError> {
+    println!("Off-CPU / Scheduling-Latency Monitoring");
+    println!("{}", "=".repeat(60));
+
+    let mut sched_runner = SchedRunner::from_binary_extractor(binary_extractor.get_sched_path());
+
+    let mut sched_args = Vec::new();
+    if let Some(comm_filter) = comm {
+        sched_args.extend(["-c".to_string(), comm_filter.to_string()]);
+        println!("Monitoring process: {}", comm_filter);
+    }
+    if let Some(pid_filter) = pid {
+        sched_args.extend(["-p".to_string(), pid_filter.to_string()]);
+        println!("Monitoring PID: {}", pid_filter);
+    }
+    sched_args.extend(["--min-latency-us".to_string(), min_latency_us.to_string()]);
+    sched_runner = sched_runner.with_args(&sched_args);
+
+    println!("Minimum reported stall: {}us", min_latency_us);
+    println!("{}", "=".repeat(60));
+    println!("Starting off-CPU monitoring (press Ctrl+C to stop):");
+
+    // Set up event broadcasting for server if enabled
+    let (event_sender, _event_receiver) = broadcast::channel(max_events_buffer);
+
+    // Add TimestampNormalizer first
+    sched_runner = sched_runner
+        .add_analyzer(Box::new(TimestampNormalizer::new()))
+        .add_analyzer(Box::new(MetricsCollector::new()));
+
+    // Add file logger
+    sched_runner = sched_runner
+        .add_analyzer(Box::new(
+            if rotate_logs {
+                FileLogger::with_max_size(output, max_log_size).unwrap()
+            } else {
+                FileLogger::new(output).unwrap()
+            }
+        ));
+
+    // Add console output unless quiet
+    if !quiet {
+        sched_runner = sched_runner.add_analyzer(Box::new(OutputAnalyzer::new()));
+    }
+
+    // Start web server if enabled
+    let server_handle = start_web_server_if_enabled(
         enable_server,
         server_port,
         log_file.or(Some(output)),
-        event_sender.clone()
+        event_sender.clone(),
     ).await
         .map_err(|e| RunnerError::from(format!("Failed to start server: {}", e)))?;
 
-    let mut stream = system_runner.run().await?;
+    let stream = sched_runner.run().await?;
+
+    consume_with_shutdown(&mut sched_runner, stream, &event_sender, enable_server, max_events_buffer, server_handle).await;
+
+    Ok(())
+}
+
+/// Replay a previously recorded log file through the same analyzer chain
+/// the live commands build, reporting per-analyzer throughput and latency
+/// instead of driving a live eBPF runner.
+async fn run_replay(
+    log_file: &str,
+    enable_sse_merge: bool,
+    enable_http_parser: bool,
+    http_filter_patterns: &Vec<String>,
+    disable_auth_removal: bool,
+    ssl_filter_patterns: &Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Replaying {}", log_file);
+    println!("{}", "=".repeat(60));
+
+    let mut chain: Vec<(&'static str, Box<dyn Analyzer>)> =
+        vec![("TimestampNormalizer", Box::new(TimestampNormalizer::new()))];
+
+    if !ssl_filter_patterns.is_empty() {
+        chain.push(("SSLFilter", Box::new(SSLFilter::with_patterns(ssl_filter_patterns.clone())?)));
+    }
+
+    if enable_sse_merge || enable_http_parser {
+        chain.push(("SSEProcessor", Box::new(SSEProcessor::new_with_timeout(30000))));
+    }
+
+    if enable_http_parser {
+        chain.push(("HTTPParser", Box::new(HTTPParser::new())));
 
-    // Consume the stream to actually process events
-    while let Some(event) = stream.next().await {
-        // Forward events to web server if enabled
-        if enable_server {
-            let _ = event_sender.send(event);
+        if !http_filter_patterns.is_empty() {
+            chain.push(("HTTPFilter", Box::new(HTTPFilter::with_patterns(http_filter_patterns.clone())?)));
+        }
+
+        if !disable_auth_removal {
+            chain.push(("AuthHeaderRemover", Box::new(AuthHeaderRemover::new())));
         }
     }
 
+    let (reports, summary) = run_replay_chain(log_file, chain)
+        .await
+        .map_err(|e| format!("Replay failed: {}", e))?;
+
+    print_replay_report(&reports, &summary);
+
     Ok(())
 }
 
@@ -0,0 +1,184 @@
+//! GraphQL query/subscription schema over the event stream.
+//!
+//! `server::web::WebServer` in this checkout has no route table to mount a
+//! `/graphql` endpoint on (it's missing from this source tree entirely -
+//! see the TLS-support gap already noted in
+//! `main.rs::start_web_server_if_enabled` for the same kind of limitation).
+//! Until that module exists, this is a self-contained schema: given the
+//! `broadcast::Sender<Event>` every command already builds for the web
+//! server, [`build_schema`] wires up a ring buffer and the query/
+//! subscription roots below. Once `server::web::WebServer` grows a route
+//! table, mounting this is `async_graphql_warp`/`async_graphql_hyper`
+//! boilerplate away from [`AgentSightSchema`].
+use crate::framework::analyzers::metrics::system_stats_for_pid;
+use crate::framework::core::Event;
+use async_graphql::{Context, Object, Schema, SimpleObject, Subscription};
+use futures::stream::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+/// GraphQL-facing projection of [`Event`]. `data` is re-serialized to a
+/// JSON string rather than mapped field-by-field since its shape varies
+/// by runner/analyzer and async-graphql has no first-class `serde_json::Value`
+/// scalar in this checkout.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EventGql {
+    pub source: String,
+    pub pid: i32,
+    pub comm: String,
+    pub timestamp: f64,
+    pub data: String,
+}
+
+impl From<&Event> for EventGql {
+    fn from(event: &Event) -> Self {
+        Self {
+            source: event.source.clone(),
+            pid: event.pid as i32,
+            comm: event.comm.clone(),
+            timestamp: event.timestamp as f64,
+            data: event.to_json().unwrap_or_else(|e| format!("{{\"error\":\"{}\"}}", e)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SystemStatsGql {
+    pub pid: i32,
+    pub cpu_percent: f64,
+    pub memory_rss_mb: f64,
+}
+
+/// Bounded most-recent-events buffer the ring is filled from, independent
+/// of (and downstream of) the same `event_sender` the web server's raw
+/// broadcast already taps.
+#[derive(Clone)]
+pub struct EventRingBuffer {
+    inner: Arc<Mutex<VecDeque<Event>>>,
+    capacity: usize,
+}
+
+impl EventRingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    fn push(&self, event: Event) {
+        if let Ok(mut buf) = self.inner.lock() {
+            if buf.len() >= self.capacity {
+                buf.pop_front();
+            }
+            buf.push_back(event);
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Event> {
+        self.inner.lock().map(|buf| buf.iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+/// Spawn the background task that keeps `ring` filled from `receiver`,
+/// for as long as `event_sender` (or any other handle) keeps the channel
+/// open. Mirrors the "tap a broadcast receiver in a spawned task" shape
+/// already used by `main.rs::start_web_server_if_enabled`.
+pub fn spawn_ring_buffer_filler(mut receiver: broadcast::Receiver<Event>, ring: EventRingBuffer) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => ring.push(event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Most recent events, optionally filtered by `pid`, `comm`, `runner_type`
+    /// (matched against `Event::source`) and `since` (epoch ms), newest last,
+    /// capped at `limit` (default 100).
+    async fn recent_events(
+        &self,
+        ctx: &Context<'_>,
+        pid: Option<i32>,
+        comm: Option<String>,
+        runner_type: Option<String>,
+        since: Option<f64>,
+        limit: Option<i32>,
+    ) -> Vec<EventGql> {
+        let ring = ctx.data_unchecked::<EventRingBuffer>();
+        let limit = limit.unwrap_or(100).max(0) as usize;
+
+        ring.snapshot()
+            .into_iter()
+            .filter(|e| pid.map_or(true, |p| e.pid as i32 == p))
+            .filter(|e| comm.as_deref().map_or(true, |c| e.comm == c))
+            .filter(|e| runner_type.as_deref().map_or(true, |t| e.source == t))
+            .filter(|e| since.map_or(true, |s| (e.timestamp as f64) >= s))
+            .rev()
+            .take(limit)
+            .map(|e| EventGql::from(&e))
+            .collect()
+    }
+
+    /// Latest CPU/memory sample `MetricsCollector` has tallied for `pid`.
+    async fn system_stats(&self, pid: i32) -> Option<SystemStatsGql> {
+        let (cpu_percent, memory_rss_mb) = system_stats_for_pid(pid as u32)?;
+        Some(SystemStatsGql {
+            pid,
+            cpu_percent,
+            memory_rss_mb: memory_rss_mb as f64,
+        })
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Live event feed, filtered server-side the same way `recent_events`
+    /// filters the ring buffer, so clients never have to filter the whole
+    /// firehose themselves.
+    async fn live_events(
+        &self,
+        ctx: &Context<'_>,
+        pid: Option<i32>,
+        comm: Option<String>,
+        runner_type: Option<String>,
+        only_threshold_breaches: Option<bool>,
+    ) -> impl Stream<Item = EventGql> {
+        let sender = ctx.data_unchecked::<broadcast::Sender<Event>>();
+        let only_breaches = only_threshold_breaches.unwrap_or(false);
+
+        BroadcastStream::new(sender.subscribe())
+            .filter_map(|result| async move { result.ok() })
+            .filter(move |e| {
+                let keep = pid.map_or(true, |p| e.pid as i32 == p)
+                    && comm.as_deref().map_or(true, |c| e.comm == c)
+                    && runner_type.as_deref().map_or(true, |t| e.source == t)
+                    && (!only_breaches || e.data.get("alert").and_then(|v| v.as_bool()).unwrap_or(false));
+                async move { keep }
+            })
+            .map(|e| EventGql::from(&e))
+    }
+}
+
+pub type AgentSightSchema = Schema<QueryRoot, async_graphql::EmptyMutation, SubscriptionRoot>;
+
+/// Build the schema, seeding its context with the ring buffer (for
+/// `recentEvents`/`systemStats`) and the raw `event_sender` (for
+/// `liveEvents` subscriptions, each of which subscribes independently).
+pub fn build_schema(ring: EventRingBuffer, event_sender: broadcast::Sender<Event>) -> AgentSightSchema {
+    Schema::build(QueryRoot, async_graphql::EmptyMutation, SubscriptionRoot)
+        .data(ring)
+        .data(event_sender)
+        .finish()
+}
@@ -0,0 +1,133 @@
+//! Single-flight coalescing for concurrent "load this log file" requests.
+//!
+//! Like [`graphql`](super::graphql), this exists independently of
+//! `server::web::WebServer` (missing from this checkout - see the
+//! TLS-support gap noted in `main.rs::start_web_server_if_enabled` for the
+//! same kind of limitation) because the actual duplicate work it avoids -
+//! each browser tab re-reading and tailing the same `log_file` - happens in
+//! `WebServer`'s request handlers, which don't exist here to modify.
+//! [`LogTailCoalescer`] is the generic primitive a future
+//! `WebServer::tail_log` route should call through rather than reading the
+//! file itself.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+/// Coalesces concurrent loads keyed by path: the first caller for a given
+/// path spawns its loader future on its own task, and every concurrent
+/// caller (for that same path) awaits the same result rather than
+/// triggering its own read. The entry is removed as soon as the loader
+/// finishes - success or failure - so the next call always re-initiates;
+/// a transient error is never cached. Because the load runs on a detached
+/// `tokio::spawn` task rather than directly inside `load()`, a caller that
+/// drops its future (a cancelled/disconnected client) does not cancel the
+/// read for anyone else still waiting on it.
+pub struct LogTailCoalescer {
+    inflight: Mutex<HashMap<String, watch::Receiver<Option<Result<Arc<String>, String>>>>>,
+}
+
+impl LogTailCoalescer {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            inflight: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Load `path`, coalescing with any other in-flight load of the same
+    /// path. `loader` only runs at all if no load for `path` is already
+    /// in flight; otherwise this just awaits the in-flight one's result.
+    pub async fn load<F, Fut>(self: &Arc<Self>, path: &str, loader: F) -> Result<Arc<String>, String>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<String, String>> + Send + 'static,
+    {
+        let mut rx = {
+            let mut inflight = self.inflight.lock().unwrap();
+            match inflight.get(path) {
+                Some(existing) => existing.clone(),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    inflight.insert(path.to_string(), rx.clone());
+
+                    let coalescer = Arc::clone(self);
+                    let path_owned = path.to_string();
+                    tokio::spawn(async move {
+                        let result = loader().await.map(Arc::new);
+                        let _ = tx.send(Some(result));
+                        coalescer.inflight.lock().unwrap().remove(&path_owned);
+                    });
+
+                    rx
+                }
+            }
+        };
+
+        loop {
+            if let Some(result) = rx.borrow().clone() {
+                return result;
+            }
+            if rx.changed().await.is_err() {
+                return Err(format!("log tail loader for {} ended without producing a result", path));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn concurrent_loads_of_the_same_path_coalesce_into_one_read() {
+        let coalescer = LogTailCoalescer::new();
+        let reads = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let coalescer = Arc::clone(&coalescer);
+            let reads = Arc::clone(&reads);
+            handles.push(tokio::spawn(async move {
+                coalescer
+                    .load("trace.log", move || {
+                        let reads = Arc::clone(&reads);
+                        async move {
+                            reads.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            Ok("line1\nline2".to_string())
+                        }
+                    })
+                    .await
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap().unwrap().as_str(), "line1\nline2");
+        }
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn a_later_load_re_initiates_after_the_prior_one_completes() {
+        let coalescer = LogTailCoalescer::new();
+        let reads = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..2 {
+            let reads = Arc::clone(&reads);
+            let result = coalescer
+                .load("trace.log", move || {
+                    let reads = Arc::clone(&reads);
+                    async move {
+                        reads.fetch_add(1, Ordering::SeqCst);
+                        Ok("ok".to_string())
+                    }
+                })
+                .await;
+            assert_eq!(result.unwrap().as_str(), "ok");
+        }
+
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod assets;
 pub mod web;
+pub mod graphql;
+pub mod log_tail;
 
 // #[cfg(test)]
 // mod test_assets;